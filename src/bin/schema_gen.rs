@@ -13,6 +13,10 @@ struct Args {
 
     #[arg(long, default_value = "schema/schema.graphql")]
     out: PathBuf,
+
+    /// Fail if operations disagree on a field's type instead of widening it to `JSON`.
+    #[arg(long, default_value_t = false)]
+    strict: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -31,7 +35,8 @@ fn main() -> anyhow::Result<()> {
     }
     docs.sort();
 
-    let content = copilot_money_cli::schema_gen::render_schema_from_operations(&docs)?;
+    let content =
+        copilot_money_cli::schema_gen::render_schema_from_operations_with(&docs, args.strict)?;
     if let Some(parent) = args.out.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -0,0 +1,98 @@
+use comfy_table::{Cell, CellAlignment};
+use serde::Serialize;
+
+use crate::client::{Account, CopilotClientTrait};
+use crate::types::AccountId;
+
+use super::render::{KeyValueRow, TableRow, render_output, shorten_id_for_table};
+use super::{AccountsCmd, Cli, value_to_money_string};
+
+pub(super) fn run_accounts(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    cmd: AccountsCmd,
+) -> anyhow::Result<()> {
+    match cmd {
+        AccountsCmd::List => {
+            let rows = client
+                .list_accounts()?
+                .into_iter()
+                .map(AccountRow::from)
+                .collect::<Vec<_>>();
+            render_output(cli, rows)
+        }
+        AccountsCmd::Show(args) => {
+            let items = client.list_accounts()?;
+            let Some(a) = items.into_iter().find(|a| a.id == args.id) else {
+                anyhow::bail!("account not found");
+            };
+            render_output(cli, account_detail_rows(&a))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AccountRow {
+    id: AccountId,
+    name: String,
+    #[serde(rename = "type")]
+    account_type: String,
+    sub_type: String,
+    mask: String,
+    balance: String,
+    /// `false` once the account has been closed in Copilot.
+    active: bool,
+}
+
+impl From<Account> for AccountRow {
+    fn from(a: Account) -> Self {
+        Self {
+            active: !a.is_user_closed.unwrap_or(false),
+            id: a.id,
+            name: a.name.unwrap_or_default(),
+            account_type: a.account_type.unwrap_or_default(),
+            sub_type: a.sub_type.unwrap_or_default(),
+            mask: a.mask.unwrap_or_default(),
+            balance: value_to_money_string(a.balance),
+        }
+    }
+}
+
+impl TableRow for AccountRow {
+    const HEADERS: &'static [&'static str] = &[
+        "id", "name", "type", "sub_type", "mask", "balance", "active",
+    ];
+
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
+        vec![
+            Cell::new(shorten_id_for_table(cli, self.id.as_str())),
+            Cell::new(&self.name),
+            Cell::new(&self.account_type),
+            Cell::new(&self.sub_type),
+            Cell::new(&self.mask),
+            Cell::new(&self.balance).set_alignment(CellAlignment::Right),
+            Cell::new(self.active),
+        ]
+    }
+}
+
+fn account_detail_rows(a: &Account) -> Vec<KeyValueRow> {
+    let row = |key: &str, value: String| KeyValueRow {
+        key: key.to_string(),
+        value,
+    };
+    vec![
+        row("id", a.id.to_string()),
+        row("name", a.name.clone().unwrap_or_default()),
+        row("type", a.account_type.clone().unwrap_or_default()),
+        row("sub_type", a.sub_type.clone().unwrap_or_default()),
+        row(
+            "institution_id",
+            a.institution_id.clone().unwrap_or_default(),
+        ),
+        row("mask", a.mask.clone().unwrap_or_default()),
+        row("balance", value_to_money_string(a.balance.clone())),
+        row("active", (!a.is_user_closed.unwrap_or(false)).to_string()),
+        row("hidden", a.is_user_hidden.unwrap_or(false).to_string()),
+    ]
+}
@@ -1,8 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
+use serde::Deserialize;
 
-use crate::client::CopilotClient;
+use crate::client::CopilotClientTrait;
 use crate::config::{
     ensure_private_dir, load_token, save_token, session_path, token_helper_path, token_path,
 };
@@ -10,7 +11,73 @@ use crate::config::{
 use super::render::{KeyValueRow, render_output};
 use super::{AuthCmd, AuthLoginMode, Cli};
 
-pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyhow::Result<()> {
+/// Credentials for `--mode credentials`: JSON `{"email": "...", "password": "..."}`, or the
+/// older `email=...` / `password=...` lines. Deliberately not `Debug` so the password can't be logged.
+#[derive(Deserialize)]
+struct SecretFile {
+    email: String,
+    password: String,
+}
+
+impl SecretFile {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read secrets file {}", path.display()))?;
+        let secrets = if raw.trim_start().starts_with('{') {
+            serde_json::from_str::<SecretFile>(&raw).map_err(|e| {
+                anyhow::anyhow!(
+                    "malformed secrets file {} (expected {{\"email\", \"password\"}}): {e}",
+                    path.display()
+                )
+            })?
+        } else {
+            let mut email = String::new();
+            let mut password = String::new();
+            for line in raw.lines().map(str::trim) {
+                match line.split_once('=') {
+                    Some((k, v)) if k.trim().eq_ignore_ascii_case("email") => {
+                        email = v.trim().to_string()
+                    }
+                    Some((k, v)) if k.trim().eq_ignore_ascii_case("password") => {
+                        password = v.trim().to_string()
+                    }
+                    _ => {}
+                }
+            }
+            SecretFile { email, password }
+        };
+        if secrets.email.trim().is_empty() || secrets.password.is_empty() {
+            anyhow::bail!(
+                "secrets file {} must contain both email and password",
+                path.display()
+            );
+        }
+        Ok(secrets)
+    }
+}
+
+/// Reads the sign-in email from stdin (visible input, unlike the token prompt).
+fn prompt_email() -> anyhow::Result<String> {
+    use std::io::Write;
+
+    eprint!("Email: ");
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .context("failed to read email")?;
+    let email = line.trim();
+    if email.is_empty() {
+        anyhow::bail!("empty email (pass --email <EMAIL>)");
+    }
+    Ok(email.to_string())
+}
+
+pub(super) fn run_auth(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    cmd: AuthCmd,
+) -> anyhow::Result<()> {
     match cmd {
         AuthCmd::Status => {
             let token = match cli.token.clone() {
@@ -34,12 +101,28 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
                     .map(|v| v.to_string())
                     .unwrap_or_else(|| "unknown".to_string()),
             });
+            // Best effort: the token is already known to work, so a failed lookup only hides the rows.
+            if valid == Some(true)
+                && let Ok(user) = client.get_user_info()
+            {
+                rows.push(KeyValueRow {
+                    key: "user_id".to_string(),
+                    value: user.id,
+                });
+                rows.push(KeyValueRow {
+                    key: "user_email".to_string(),
+                    value: user.email.unwrap_or_default(),
+                });
+            }
 
             render_output(cli, rows)
         }
         AuthCmd::Login(args) => {
             if cli.dry_run {
-                println!("dry-run: would obtain token via browser helper (tools/get_token.py)");
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would obtain token via browser helper (tools/get_token.py)"
+                )?;
                 return Ok(());
             }
 
@@ -62,7 +145,12 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
                     }
                     AuthLoginMode::EmailLink => {
                         cmd.args(["--mode", "email-link"]);
-                        if let Some(email) = &args.email {
+                        let email = match (&args.email, &args.secrets_file) {
+                            (Some(e), _) => Some(e.clone()),
+                            (None, Some(_)) => None,
+                            (None, None) => Some(prompt_email()?),
+                        };
+                        if let Some(email) = &email {
                             cmd.args(["--email", email]);
                         }
                         if let Some(p) = args.secrets_file {
@@ -70,7 +158,9 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
                         }
                     }
                     AuthLoginMode::Credentials => {
-                        cmd.args(["--mode", "credentials"]);
+                        eprintln!(
+                            "warning: --mode credentials reads a plaintext password from disk; prefer --mode email-link",
+                        );
                         let p = args.secrets_file.clone().unwrap_or_else(|| {
                             let mut p = PathBuf::from(std::env::var_os("HOME").unwrap_or_default());
                             p.push(".codex");
@@ -78,7 +168,10 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
                             p.push("copilot_money");
                             p
                         });
-                        cmd.args(["--secrets-file", p.to_string_lossy().as_ref()]);
+                        let secrets = SecretFile::load(&p)?;
+                        cmd.args(["--mode", "credentials", "--email", &secrets.email]);
+                        // Via the environment rather than argv so it doesn't show up in `ps`.
+                        cmd.env("COPILOT_LOGIN_PASSWORD", &secrets.password);
                     }
                 };
 
@@ -115,15 +208,24 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
                 token = Some(t.trim().to_string());
             }
 
+            let token = token.unwrap();
+            if args.no_save {
+                writeln!(cli.out.lock(), "{token}")?;
+                return Ok(());
+            }
+
             let p = cli.token_file.clone().unwrap_or_else(token_path);
-            save_token(&p, token.as_ref().unwrap())?;
+            save_token(&p, &token)?;
 
-            println!("saved token to {}", p.display());
+            writeln!(cli.out.lock(), "saved token to {}", p.display())?;
             Ok(())
         }
         AuthCmd::Refresh(args) => {
             if cli.dry_run {
-                println!("dry-run: would refresh token via persisted session");
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would refresh token via persisted session"
+                )?;
                 return Ok(());
             }
 
@@ -160,12 +262,15 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
 
             let p = cli.token_file.clone().unwrap_or_else(token_path);
             save_token(&p, &token)?;
-            println!("refreshed token (saved to {})", p.display());
+            writeln!(cli.out.lock(), "refreshed token (saved to {})", p.display())?;
             Ok(())
         }
         AuthCmd::SetToken(args) => {
             if cli.dry_run {
-                println!("dry-run: would prompt for token and write it to disk");
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would prompt for token and write it to disk"
+                )?;
                 return Ok(());
             }
 
@@ -184,7 +289,7 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
                 .or_else(|| cli.token_file.clone())
                 .unwrap_or_else(token_path);
             save_token(&p, token.trim())?;
-            println!("saved token to {}", p.display());
+            writeln!(cli.out.lock(), "saved token to {}", p.display())?;
             Ok(())
         }
         AuthCmd::Logout => {
@@ -192,7 +297,7 @@ pub(super) fn run_auth(cli: &Cli, client: &CopilotClient, cmd: AuthCmd) -> anyho
             if p.exists() {
                 std::fs::remove_file(&p)?;
             }
-            println!("removed token at {}", p.display());
+            writeln!(cli.out.lock(), "removed token at {}", p.display())?;
             Ok(())
         }
     }
@@ -0,0 +1,122 @@
+use std::io::BufRead;
+
+use anyhow::Context;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches};
+use serde::Deserialize;
+
+use crate::client::CopilotClientTrait;
+
+use super::render::OutputSink;
+use super::{BatchArgs, Cli, Command};
+
+/// One line of a batch file: `{"command": ["transactions", "review", "txn_1"]}`.
+#[derive(Debug, Deserialize)]
+struct BatchLine {
+    command: Vec<String>,
+}
+
+pub(super) fn run_batch(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    args: BatchArgs,
+) -> anyhow::Result<()> {
+    if !cli.yes && !cli.dry_run {
+        anyhow::bail!("batch runs write commands without prompting; pass --yes (or --dry-run)");
+    }
+
+    let file = std::fs::File::open(&args.file)
+        .with_context(|| format!("failed to open {}", args.file.display()))?;
+
+    let (mut ok, mut failed) = (0usize, 0usize);
+    for (i, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let lineno = i + 1;
+        match run_line(cli, client, &line) {
+            Ok(()) => ok += 1,
+            Err(e) => {
+                failed += 1;
+                eprintln!("batch line {lineno}: {e:#}");
+                if args.fail_fast {
+                    break;
+                }
+            }
+        }
+    }
+
+    eprintln!("batch: {ok} succeeded, {failed} failed");
+    if failed > 0 {
+        anyhow::bail!("{failed} batch command(s) failed");
+    }
+    Ok(())
+}
+
+/// Global flags that configure the shared client (or error reporting) and so can't differ per line.
+const BATCH_ONLY_GLOBALS: &[&str] = &[
+    "base_url",
+    "token",
+    "token_file",
+    "session_dir",
+    "max_retries",
+    "retry_delay_ms",
+    "request_timeout_seconds",
+    "connection_timeout_seconds",
+    "fixtures_dir",
+    "format_errors",
+];
+
+/// Parses a line with the normal CLI parser and runs it with the batch's global flags, overridden
+/// by any output-related globals the line sets itself. A line's `--dry-run` always applies.
+fn run_line(cli: &Cli, client: &dyn CopilotClientTrait, line: &str) -> anyhow::Result<()> {
+    let parsed: BatchLine = serde_json::from_str(line).context("invalid batch line")?;
+    let argv = std::iter::once("copilot".to_string()).chain(parsed.command);
+    let matches = Cli::command()
+        .try_get_matches_from(argv)
+        .map_err(|e| anyhow::anyhow!(e.to_string().trim().to_string()))?;
+    let sub = Cli::from_arg_matches(&matches)?;
+    if matches!(sub.command, Command::Batch(_) | Command::Watch(_)) {
+        anyhow::bail!("batch files cannot run `batch` or `watch`");
+    }
+    let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+    if let Some(id) = BATCH_ONLY_GLOBALS.iter().find(|id| given(id)) {
+        anyhow::bail!(
+            "--{} can't be set per batch line; pass it to `copilot batch` instead",
+            id.replace('_', "-")
+        );
+    }
+
+    let mut line_cli = Cli {
+        command: sub.command,
+        ..cli.clone()
+    };
+    line_cli.dry_run |= sub.dry_run;
+    line_cli.yes |= sub.yes;
+    line_cli.no_truncate_ids |= sub.no_truncate_ids;
+    if given("output") {
+        line_cli.output = sub.output;
+    }
+    if given("color") {
+        line_cli.color = sub.color;
+    }
+    if given("date_order") {
+        line_cli.date_order = sub.date_order;
+    }
+    if given("global_date_from") {
+        line_cli.global_date_from = sub.global_date_from;
+    }
+    if given("global_date_to") {
+        line_cli.global_date_to = sub.global_date_to;
+    }
+    if given("output_file") || given("append_output_file") {
+        line_cli.out = match sub.append_output_file.as_deref() {
+            Some(path) => OutputSink::append(path)?,
+            None => OutputSink::open(sub.output_file.as_deref())?,
+        };
+        line_cli.output_file = sub.output_file;
+        line_cli.append_output_file = sub.append_output_file;
+    }
+    super::dispatch(&line_cli, client)
+}
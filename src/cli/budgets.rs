@@ -1,21 +1,41 @@
-use comfy_table::Cell;
-use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
 
-use crate::client::CopilotClient;
+use anyhow::Context;
+use comfy_table::{Cell, CellAlignment, Color};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Category, CategoryPeriods, CopilotClientTrait};
+use crate::types::CategoryId;
 
 use super::render::{TableRow, render_output};
-use super::{BudgetsCmd, Cli};
+use super::{
+    BudgetsCmd, BudgetsHistoryArgs, BudgetsListArgs, BudgetsSetFromCsvArgs, Cli, value_to_f64,
+    value_to_money_string,
+};
 
 pub(super) fn run_budgets(
     cli: &Cli,
-    client: &CopilotClient,
+    client: &dyn CopilotClientTrait,
     cmd: BudgetsCmd,
 ) -> anyhow::Result<()> {
     match cmd {
+        BudgetsCmd::List(args) => {
+            let items = client.list_categories(true, true, false)?;
+            let mut rows = Vec::new();
+            collect_budget_rows(&mut rows, &items, &args);
+            render_output(cli, rows)
+        }
         BudgetsCmd::Month => {
+            let (from, to) = super::resolve_date_range(cli, None, None)?;
+            // Months are `YYYY-MM`; compare against the month part of the range.
+            let from = from.map(|d| d[..7].to_string());
+            let to = to.map(|d| d[..7].to_string());
             let items = client.list_budget_months()?;
             let rows = items
                 .into_iter()
+                .filter(|b| from.as_deref().is_none_or(|f| b.month.as_str() >= f))
+                .filter(|b| to.as_deref().is_none_or(|t| b.month.as_str() <= t))
                 .map(|b| BudgetRow {
                     month: b.month,
                     amount: b.amount,
@@ -23,10 +43,179 @@ pub(super) fn run_budgets(
                 .collect::<Vec<_>>();
             render_output(cli, rows)
         }
+        BudgetsCmd::History(args) => {
+            let items = client.list_categories(true, true, false)?;
+            let Some(c) = super::categories::find_category(&items, &args.category_id) else {
+                anyhow::bail!("category not found");
+            };
+            render_output(cli, budget_history_rows(c, &args))
+        }
         BudgetsCmd::Set => anyhow::bail!("budgets set not implemented yet (need mutation doc)"),
+        BudgetsCmd::SetFromCsv(args) => {
+            let plan = plan_budgets_from_csv(client, &args)?;
+            if cli.dry_run {
+                let mut out = cli.out.lock();
+                writeln!(
+                    out,
+                    "dry-run: would set {} budget(s) for {}:",
+                    plan.budgets.len(),
+                    plan.month
+                )?;
+                for b in &plan.budgets {
+                    writeln!(out, "  {} ({}): {}", b.name, b.category_id, money(b.amount))?;
+                }
+                return Ok(());
+            }
+            // Everything above is validated, but there's no captured budget mutation to send.
+            anyhow::bail!(
+                "budgets set-from-csv not implemented yet (need mutation doc); use --dry-run to check the plan"
+            )
+        }
+        BudgetsCmd::SetRollover(args) => {
+            let action = if args.enable { "enable" } else { "disable" };
+            if cli.dry_run {
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would {action} rollovers for category {}",
+                    args.category_id
+                )?;
+                return Ok(());
+            }
+            anyhow::bail!("budgets rollover not implemented yet (need mutation doc)")
+        }
     }
 }
 
+#[derive(Deserialize)]
+struct RawBudgetRecord {
+    #[serde(default)]
+    category_id: Option<String>,
+    #[serde(default)]
+    category_name: Option<String>,
+    amount: String,
+}
+
+struct BudgetRecord {
+    category_id: Option<String>,
+    category_name: Option<String>,
+    amount: f64,
+}
+
+struct PlannedBudget {
+    category_id: CategoryId,
+    name: String,
+    amount: f64,
+}
+
+struct BudgetPlan {
+    month: String,
+    budgets: Vec<PlannedBudget>,
+}
+
+/// Reads the CSV and resolves every row's category (one `Categories` fetch), failing on the
+/// first bad row so nothing is half-applied.
+fn plan_budgets_from_csv(
+    client: &dyn CopilotClientTrait,
+    args: &BudgetsSetFromCsvArgs,
+) -> anyhow::Result<BudgetPlan> {
+    if let Some(m) = args.month.as_deref()
+        && !is_month(m)
+    {
+        anyhow::bail!("invalid --month {m:?} (expected YYYY-MM)");
+    }
+    let records = read_budget_csv(&args.file)?;
+    let categories = client.list_categories(false, true, false)?;
+    let month = match args.month.clone() {
+        Some(m) => m,
+        None => categories
+            .iter()
+            .find_map(|c| c.budget.as_ref()?.current.as_ref()?.month.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!("could not determine the current budget month; pass --month")
+            })?,
+    };
+
+    let mut budgets = Vec::new();
+    for (line, record) in records {
+        let at = format!("{}: line {line}", args.file.display());
+        let category_id = match (record.category_id, record.category_name) {
+            (Some(id), _) => CategoryId::from(id),
+            (None, Some(name)) => super::match_category_name(&categories, &name)
+                .map_err(|e| anyhow::anyhow!("{at}: {e}"))?,
+            (None, None) => unreachable!("checked by read_budget_csv"),
+        };
+        let Some(category) = super::categories::find_category(&categories, &category_id) else {
+            anyhow::bail!("{at}: unknown category id {category_id}");
+        };
+        budgets.push(PlannedBudget {
+            name: category.name.clone().unwrap_or_default(),
+            category_id,
+            amount: record.amount,
+        });
+    }
+    Ok(BudgetPlan { month, budgets })
+}
+
+/// Rows paired with their line numbers (line 1 is the header).
+fn read_budget_csv(path: &Path) -> anyhow::Result<Vec<(usize, BudgetRecord)>> {
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let mut records = Vec::new();
+    for (i, row) in reader.deserialize::<RawBudgetRecord>().enumerate() {
+        let line = i + 2;
+        let row = row.with_context(|| format!("{}: line {line}", path.display()))?;
+        let non_empty =
+            |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let category_id = non_empty(row.category_id);
+        let category_name = non_empty(row.category_name);
+        if category_id.is_some() == category_name.is_some() {
+            anyhow::bail!(
+                "{}: line {line}: expected exactly one of category_id or category_name",
+                path.display()
+            );
+        }
+        let amount = row
+            .amount
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|a| a.is_finite() && *a >= 0.0)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}: line {line}: invalid amount {:?}",
+                    path.display(),
+                    row.amount
+                )
+            })?;
+        records.push((
+            line,
+            BudgetRecord {
+                category_id,
+                category_name,
+                amount,
+            },
+        ));
+    }
+    if records.is_empty() {
+        anyhow::bail!("{}: no rows", path.display());
+    }
+    Ok(records)
+}
+
+fn is_month(s: &str) -> bool {
+    matches!(
+        s.split_once('-'),
+        Some((y, m)) if y.len() == 4
+            && y.bytes().all(|b| b.is_ascii_digit())
+            && m.len() == 2
+            && matches!(m.parse::<u8>(), Ok(1..=12))
+    )
+}
+
+fn money(amount: f64) -> String {
+    value_to_money_string(Some(serde_json::Value::String(amount.to_string())))
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct BudgetRow {
     month: String,
@@ -36,7 +225,137 @@ struct BudgetRow {
 impl TableRow for BudgetRow {
     const HEADERS: &'static [&'static str] = &["month", "amount"];
 
-    fn cells(&self) -> Vec<Cell> {
+    fn cells(&self, _cli: &Cli) -> Vec<Cell> {
         vec![Cell::new(&self.month), Cell::new(&self.amount)]
     }
 }
+
+#[derive(Debug, Clone, Serialize)]
+struct CategoryBudgetRow {
+    category_id: CategoryId,
+    name: String,
+    spend: String,
+    budget: String,
+    remaining: String,
+}
+
+impl TableRow for CategoryBudgetRow {
+    const HEADERS: &'static [&'static str] = &["category", "spend", "budget", "remaining"];
+
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
+        let mut remaining = Cell::new(&self.remaining).set_alignment(CellAlignment::Right);
+        if super::should_color(cli) && !self.remaining.is_empty() {
+            remaining = remaining.fg(if self.remaining.starts_with("-$") {
+                Color::Red
+            } else {
+                Color::Green
+            });
+        }
+        vec![
+            Cell::new(&self.name),
+            Cell::new(&self.spend).set_alignment(CellAlignment::Right),
+            Cell::new(&self.budget).set_alignment(CellAlignment::Right),
+            remaining,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BudgetHistoryRow {
+    month: String,
+    budget_amount: String,
+    spend_amount: String,
+    remaining: String,
+}
+
+impl TableRow for BudgetHistoryRow {
+    const HEADERS: &'static [&'static str] =
+        &["month", "budget_amount", "spend_amount", "remaining"];
+
+    fn cells(&self, _cli: &Cli) -> Vec<Cell> {
+        vec![
+            Cell::new(&self.month),
+            Cell::new(&self.budget_amount).set_alignment(CellAlignment::Right),
+            Cell::new(&self.spend_amount).set_alignment(CellAlignment::Right),
+            Cell::new(&self.remaining).set_alignment(CellAlignment::Right),
+        ]
+    }
+}
+
+/// Amount per month from `histories` plus `current` (which wins if both list a month).
+fn monthly_amounts(periods: Option<&CategoryPeriods>) -> BTreeMap<String, f64> {
+    let Some(p) = periods else {
+        return BTreeMap::new();
+    };
+    p.histories
+        .iter()
+        .flatten()
+        .chain(p.current.as_ref())
+        .filter_map(|m| Some((m.month.clone()?, value_to_f64(m.amount.as_ref())?)))
+        .collect()
+}
+
+/// Oldest first, limited to the `--months` most recent months with a budget or spend.
+fn budget_history_rows(c: &Category, args: &BudgetsHistoryArgs) -> Vec<BudgetHistoryRow> {
+    let budget = monthly_amounts(c.budget.as_ref());
+    let spend = monthly_amounts(c.spend.as_ref());
+    let mut months = budget.keys().chain(spend.keys()).collect::<Vec<_>>();
+    months.sort();
+    months.dedup();
+    let skip = months.len().saturating_sub(args.months);
+
+    months
+        .into_iter()
+        .skip(skip)
+        .map(|m| {
+            let b = budget.get(m).copied();
+            let s = spend.get(m).copied();
+            BudgetHistoryRow {
+                month: m.clone(),
+                budget_amount: b.map(money).unwrap_or_default(),
+                spend_amount: s.map(money).unwrap_or_default(),
+                remaining: b.map(|b| money(b - s.unwrap_or(0.0))).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+fn current_amount(periods: Option<&CategoryPeriods>) -> Option<f64> {
+    value_to_f64(periods?.current.as_ref()?.amount.as_ref())
+}
+
+/// Flattens categories (children included) into rows, applying `--over-budget`/`--under-budget`.
+fn collect_budget_rows(
+    out: &mut Vec<CategoryBudgetRow>,
+    categories: &[Category],
+    args: &BudgetsListArgs,
+) {
+    for c in categories {
+        let spend = current_amount(c.spend.as_ref());
+        let budget = current_amount(c.budget.as_ref());
+        let keep = if args.over_budget || args.under_budget {
+            let spent = spend.unwrap_or(0.0);
+            match budget.filter(|b| *b != 0.0) {
+                None => false,
+                Some(b) if args.over_budget => spent > b,
+                Some(b) => spent < b,
+            }
+        } else {
+            true
+        };
+        if keep {
+            out.push(CategoryBudgetRow {
+                category_id: c.id.clone(),
+                name: c.name.clone().unwrap_or_default(),
+                spend: spend.map(money).unwrap_or_default(),
+                budget: budget.map(money).unwrap_or_default(),
+                remaining: budget
+                    .map(|b| money(b - spend.unwrap_or(0.0)))
+                    .unwrap_or_default(),
+            });
+        }
+        if let Some(children) = c.child_categories.as_deref() {
+            collect_budget_rows(out, children, args);
+        }
+    }
+}
@@ -1,91 +1,176 @@
-use comfy_table::Cell;
+use std::collections::HashMap;
+use std::io::Write;
+
+use anyhow::Context;
+use comfy_table::{Cell, CellAlignment, Color, Row as ComfyRow};
 use serde::Serialize;
+use serde_json::Value;
 
-use crate::client::{Category, CopilotClient};
-use crate::types::CategoryId;
+use crate::client::{Category, CategoryPeriods, CopilotClientTrait, Icon};
+use crate::types::{CategoryId, TransactionId};
 
-use super::render::{KeyValueRow, TableRow, render_output, shorten_id_for_table};
-use super::{CategoriesCmd, Cli};
+use super::render::{
+    KeyValueRow, TableRow, header_cell, new_table, render_output, shorten_id_for_table,
+};
+use super::{
+    CategoriesCmd, CategoriesCreateArgs, CategoriesExportArgs, CategorySort, Cli, OutputFormat,
+    value_to_f64, value_to_money_string,
+};
 
 pub(super) fn run_categories(
     cli: &Cli,
-    client: &CopilotClient,
+    client: &dyn CopilotClientTrait,
     cmd: CategoriesCmd,
 ) -> anyhow::Result<()> {
     match cmd {
         CategoriesCmd::List(args) => {
+            if args.sort == Some(CategorySort::Spend) && !args.spend {
+                anyhow::bail!("--sort spend requires --spend");
+            }
             let items = client.list_categories(args.spend, args.budget, args.rollovers)?;
+            // The `--total` and percentage base: all spend, not just the rows left after filters.
+            let total = total_spend(&items);
             let mut flat = flatten_categories(&items, args.children);
 
             if let Some(q) = args.name_contains.as_ref() {
                 let q = q.to_lowercase();
                 flat.retain(|c| c.name.to_lowercase().contains(&q));
             }
+            let want_excluded = match (args.excluded_only, args.included_only) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => args.excluded,
+            };
+            if let Some(want) = want_excluded {
+                flat.retain(|c| (c.is_excluded == Some(true)) == want);
+            }
+            if let Some(template_id) = args.template_id_filter.as_deref() {
+                flat.retain(|c| c.template_id.as_deref() == Some(template_id));
+            }
+            if args.no_template_id {
+                flat.retain(|c| c.template_id.is_none());
+            }
+            if args.can_delete_only || args.cannot_delete_only {
+                flat.retain(|c| (c.can_be_deleted == Some(true)) == args.can_delete_only);
+            }
+            match args.sort {
+                Some(CategorySort::Name) => flat.sort_by_key(|c| c.name.to_lowercase()),
+                Some(CategorySort::Spend) => flat.sort_by(|a, b| {
+                    let spend = |c: &FlatCategory| c.spend_amount.unwrap_or(0.0);
+                    spend(b).total_cmp(&spend(a))
+                }),
+                None => {}
+            }
+
+            if args.count_only {
+                let n = flat.len();
+                let mut out = cli.out.lock();
+                match cli.output {
+                    OutputFormat::Json => writeln!(
+                        out,
+                        "{}",
+                        serde_json::to_string_pretty(&serde_json::json!({ "count": n }))?
+                    )?,
+                    OutputFormat::Table if args.quiet => writeln!(out, "{n}")?,
+                    OutputFormat::Table => writeln!(out, "{n} categories")?,
+                }
+                return Ok(());
+            }
 
-            let rows = flat
+            let mut rows = flat
                 .into_iter()
                 .map(|c| CategoryRow {
+                    status: (args.spend && args.budget).then(|| {
+                        budget_status(c.spend_amount, c.budget_value)
+                            .unwrap_or_default()
+                            .to_string()
+                    }),
                     id: c.id,
                     name: c.name,
                     parent_id: c.parent_id,
                     excluded: c.is_excluded.unwrap_or(false).to_string(),
                     can_be_deleted: c.can_be_deleted.unwrap_or(false).to_string(),
+                    current_spend: c.current_spend,
+                    budget_amount: c.budget_amount,
+                    rollover_disabled: c.is_rollover_disabled.unwrap_or(false).to_string(),
+                    icon: c.icon,
+                    depth: args.children.then_some(c.depth),
+                    spend_pct: None,
                 })
                 .collect::<Vec<_>>();
-            render_output(cli, rows)
+            let columns = CategoryColumns {
+                icon: args.with_icons,
+                spend: args.spend,
+                spend_pct: args.with_spend_percentage,
+                budget: args.budget,
+                status: args.spend && args.budget,
+            };
+            if args.with_spend_percentage {
+                set_spend_percentages(&mut rows, &spend_by_id(&items), total);
+            }
+            if args.total {
+                let total = value_to_money_string(Some(Value::String(total.to_string())));
+                return render_categories_with_total(cli, rows, &columns, &total);
+            }
+            render_categories(cli, rows, &columns)
         }
-        CategoriesCmd::Show { id } => {
-            let items = client.list_categories(false, false, false)?;
-            let found = flatten_categories(&items, true)
+        CategoriesCmd::Show(args) => {
+            let items = client.list_categories(args.spend, args.budget, false)?;
+            let Some(c) = find_category(&items, &args.id) else {
+                anyhow::bail!("category not found");
+            };
+            let parent_id = flatten_categories(&items, true)
                 .into_iter()
-                .find(|c| c.id == id);
-            match found {
-                Some(c) => render_output(
-                    cli,
-                    vec![
-                        KeyValueRow {
-                            key: "id".to_string(),
-                            value: c.id.to_string(),
-                        },
-                        KeyValueRow {
-                            key: "name".to_string(),
-                            value: c.name,
-                        },
-                        KeyValueRow {
-                            key: "parent_id".to_string(),
-                            value: c
-                                .parent_id
-                                .as_ref()
-                                .map(|p| p.to_string())
-                                .unwrap_or_default(),
-                        },
-                        KeyValueRow {
-                            key: "is_excluded".to_string(),
-                            value: c.is_excluded.unwrap_or(false).to_string(),
-                        },
-                    ],
-                ),
-                None => anyhow::bail!("category not found"),
+                .find(|f| f.id == args.id)
+                .and_then(|f| f.parent_id);
+            let detail = CategoryDetail {
+                id: c.id.clone(),
+                name: c.name.clone().unwrap_or_default(),
+                parent_id,
+                is_excluded: c.is_excluded.unwrap_or(false),
+                // Rollovers are a budget setting; without `--budget` there's nothing to report.
+                rollover_disabled: c.is_rollover_disabled.filter(|_| args.budget),
+                child_count: c.child_categories.as_ref().map_or(0, |v| v.len()),
+                current_spend: args.spend.then(|| current_amount(c.spend.as_ref())),
+                budget_amount: args.budget.then(|| current_amount(c.budget.as_ref())),
+            };
+            if cli.output == OutputFormat::Json {
+                writeln!(cli.out.lock(), "{}", serde_json::to_string_pretty(&detail)?)?;
+                return Ok(());
             }
+            render_output(cli, detail.rows())
         }
         CategoriesCmd::Create(args) => {
+            let parent_id = super::resolve_category_id(
+                client,
+                args.parent_id.as_ref(),
+                args.parent.as_deref(),
+            )?;
+            let input = build_category_input(&args, parent_id.as_ref());
+            if args.dry_run_preview {
+                return render_category_input_preview(cli, &input);
+            }
+            let parent_label = match (&parent_id, &args.parent) {
+                (None, _) => String::new(),
+                (Some(_), Some(name)) => format!(" (parent: {name})"),
+                (Some(id), None) => {
+                    let names = super::category_name_map(client)?;
+                    let name = names.get(id).map_or(id.as_str(), String::as_str);
+                    format!(" (parent: {name})")
+                }
+            };
             if cli.dry_run {
-                println!("dry-run: would create category: {}", args.name);
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would create category: {}{parent_label}",
+                    args.name
+                )?;
                 return Ok(());
             }
-            super::confirm_write(cli, &format!("Create category: {}", args.name))?;
-
-            let mut input = serde_json::json!({
-                "name": args.name,
-                "emoji": args.emoji,
-                "colorName": args.color_name,
-                "isExcluded": args.excluded,
-                "templateId": args.template_id,
-            });
-
-            if let Some(amount) = args.budget_unassigned_amount {
-                input["budget"] = serde_json::json!({ "unassignedAmount": amount });
-            }
+            super::confirm_write(
+                cli,
+                &format!("Create category: {}{parent_label}", args.name),
+            )?;
 
             let want_budget = args.budget_unassigned_amount.is_some();
             let cat = client.create_category(input, true, want_budget)?;
@@ -113,15 +198,125 @@ pub(super) fn run_categories(
                         key: "color_name".to_string(),
                         value: cat.color_name.unwrap_or_default(),
                     },
+                    KeyValueRow {
+                        key: "parent_id".to_string(),
+                        value: parent_id.map(|p| p.to_string()).unwrap_or_default(),
+                    },
                 ],
             )
         }
+        CategoriesCmd::Export(args) => {
+            let items = client.list_categories(args.spend, args.budget, false)?;
+            let mut flat = flatten_categories(&items, true);
+            if let Some(q) = args.name_contains.as_ref() {
+                let q = q.to_lowercase();
+                flat.retain(|c| c.name.to_lowercase().contains(&q));
+            }
+            if let Some(parent_id) = args.parent_id.as_ref() {
+                flat.retain(|c| c.parent_id.as_ref() == Some(parent_id));
+            }
+
+            if args.file.as_os_str() == "-" {
+                return write_categories_csv(&mut *cli.out.lock(), &flat, &args);
+            }
+            let file = std::fs::File::create(&args.file)
+                .with_context(|| format!("failed to create {}", args.file.display()))?;
+            write_categories_csv(std::io::BufWriter::new(file), &flat, &args)?;
+            writeln!(
+                cli.out.lock(),
+                "exported {} categories to {}",
+                flat.len(),
+                args.file.display()
+            )?;
+            Ok(())
+        }
+        CategoriesCmd::Merge(args) => {
+            if args.source_id == args.target_id {
+                anyhow::bail!("source and target must be different categories");
+            }
+            let names = super::category_name_map(client)?;
+            let name = |id: &CategoryId| {
+                names
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("category {id} not found"))
+            };
+            let (source, target) = (name(&args.source_id)?, name(&args.target_id)?);
+
+            // Narrow server-side; the client-side category check below still applies.
+            let filter = serde_json::json!({ "categoryIds": [args.source_id] });
+            let (items, _) = super::fetch_transactions_with_filter_sort(
+                client,
+                200,
+                None,
+                1,
+                true,
+                Some(filter),
+                None,
+            )?;
+            let txns = super::filter_transactions(
+                items,
+                &super::TransactionFilters {
+                    category_id: Some(&args.source_id),
+                    ..Default::default()
+                },
+            );
+            if cli.dry_run {
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would move {} transaction(s) from {source} to {target}",
+                    txns.len()
+                )?;
+                return Ok(());
+            }
+            if txns.is_empty() {
+                writeln!(cli.out.lock(), "no transactions in {source}")?;
+                return Ok(());
+            }
+            super::confirm_write(
+                cli,
+                &format!(
+                    "Move {} transaction(s) from {source} to {target}",
+                    txns.len()
+                ),
+            )?;
+
+            // Keep going past failures so the summary says exactly which transactions moved.
+            let rows = txns
+                .into_iter()
+                .map(|txn| {
+                    let moved =
+                        super::require_item_and_account(&txn).and_then(|(item_id, account_id)| {
+                            client.edit_transaction(
+                                &item_id,
+                                &account_id,
+                                &txn.id,
+                                serde_json::json!({ "categoryId": args.target_id }),
+                            )
+                        });
+                    CategoryMergeRow {
+                        transaction_id: txn.id,
+                        name: txn.name.unwrap_or_default(),
+                        status: if moved.is_ok() { "moved" } else { "failed" },
+                        error: moved.err().map(|e| format!("{e:#}")),
+                    }
+                })
+                .collect::<Vec<_>>();
+            let failed = rows.iter().filter(|r| r.error.is_some()).count();
+            render_output(cli, rows)?;
+            if failed > 0 {
+                anyhow::bail!("failed to move {failed} transaction(s)");
+            }
+            Ok(())
+        }
         CategoriesCmd::Edit(args) => {
             if cli.dry_run {
-                println!(
+                writeln!(
+                    cli.out.lock(),
                     "dry-run: would edit category {} (name={:?})",
-                    args.id, args.name
-                );
+                    args.id,
+                    args.name
+                )?;
                 return Ok(());
             }
             anyhow::bail!("categories edit not implemented yet (need captured mutation document)");
@@ -129,6 +324,118 @@ pub(super) fn run_categories(
     }
 }
 
+/// The `CreateCategory` input, with `parent_id` already resolved from `--parent`/`--parent-id`.
+fn build_category_input(args: &CategoriesCreateArgs, parent_id: Option<&CategoryId>) -> Value {
+    let mut input = serde_json::json!({
+        "name": args.name,
+        "emoji": args.emoji,
+        "colorName": args.color_name,
+        "isExcluded": args.excluded,
+        "templateId": args.template_id,
+    });
+
+    if let Some(amount) = args.budget_unassigned_amount {
+        input["budget"] = serde_json::json!({ "unassignedAmount": amount });
+    }
+    if let Some(id) = parent_id {
+        input["parentId"] = serde_json::json!(id.as_str());
+    }
+    input
+}
+
+/// Shows the input `categories create` would send: the JSON itself, or one row per field.
+fn render_category_input_preview(cli: &Cli, input: &Value) -> anyhow::Result<()> {
+    if cli.output == OutputFormat::Json {
+        writeln!(cli.out.lock(), "{}", serde_json::to_string_pretty(input)?)?;
+        return Ok(());
+    }
+    let fields = [
+        "name",
+        "emoji",
+        "colorName",
+        "isExcluded",
+        "templateId",
+        "parentId",
+        "budget",
+    ];
+    let rows = fields
+        .iter()
+        .map(|&key| KeyValueRow {
+            key: key.to_string(),
+            value: match input.get(key) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(s)) => s.clone(),
+                Some(v) => v.to_string(),
+            },
+        })
+        .collect::<Vec<_>>();
+    render_output(cli, rows)
+}
+
+/// `categories show`: the same keys in every case; fields that weren't fetched are `null`.
+#[derive(Debug, Serialize)]
+struct CategoryDetail {
+    id: CategoryId,
+    name: String,
+    parent_id: Option<CategoryId>,
+    is_excluded: bool,
+    rollover_disabled: Option<bool>,
+    child_count: usize,
+    current_spend: Option<String>,
+    budget_amount: Option<String>,
+}
+
+impl CategoryDetail {
+    /// Table rows; unfetched fields are left out rather than shown blank.
+    fn rows(self) -> Vec<KeyValueRow> {
+        let row = |key: &str, value: String| KeyValueRow {
+            key: key.to_string(),
+            value,
+        };
+        let mut rows = vec![
+            row("id", self.id.to_string()),
+            row("name", self.name),
+            row(
+                "parent_id",
+                self.parent_id.map(|p| p.to_string()).unwrap_or_default(),
+            ),
+            row("is_excluded", self.is_excluded.to_string()),
+        ];
+        if let Some(v) = self.rollover_disabled {
+            rows.push(row("rollover_disabled", v.to_string()));
+        }
+        rows.push(row("child_count", self.child_count.to_string()));
+        if let Some(v) = self.current_spend {
+            rows.push(row("current_spend", v));
+        }
+        if let Some(v) = self.budget_amount {
+            rows.push(row("budget_amount", v));
+        }
+        rows
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CategoryMergeRow {
+    transaction_id: TransactionId,
+    name: String,
+    status: &'static str,
+    error: Option<String>,
+}
+
+impl TableRow for CategoryMergeRow {
+    const HEADERS: &'static [&'static str] = &["transaction_id", "name", "status", "error"];
+
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
+        vec![
+            Cell::new(shorten_id_for_table(cli, self.transaction_id.as_str())),
+            Cell::new(&self.name),
+            Cell::new(self.status),
+            Cell::new(self.error.as_deref().unwrap_or_default()),
+        ]
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct CategoryRow {
     id: CategoryId,
@@ -136,20 +443,218 @@ struct CategoryRow {
     parent_id: Option<CategoryId>,
     excluded: String,
     can_be_deleted: String,
+    /// Empty unless spend was requested with `--spend`.
+    current_spend: String,
+    /// Empty unless budgets were requested with `--budget`.
+    budget_amount: String,
+    rollover_disabled: String,
+    icon: Option<Icon>,
+    /// Nesting level (0 = top-level); only set with `--children`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    depth: Option<usize>,
+    /// Share of the non-excluded spend total (e.g. `12.3%`); only set with `--with-spend-percentage`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spend_pct: Option<String>,
+    /// `over`, `warning`, or `on-track` (empty without a budget); only set with `--spend --budget`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+}
+
+/// `over` past the budget, `warning` above 90% of it, `on-track` otherwise. `None` without a
+/// positive budget, since there is nothing to be over.
+fn budget_status(spend: Option<f64>, budget: Option<f64>) -> Option<&'static str> {
+    let budget = budget.filter(|b| *b > 0.0)?;
+    let spend = spend.unwrap_or(0.0);
+    Some(if spend > budget {
+        "over"
+    } else if spend > budget * 0.9 {
+        "warning"
+    } else {
+        "on-track"
+    })
+}
+
+/// Optional columns for `categories list` table output (JSON always includes every field).
+#[derive(Debug, Default)]
+struct CategoryColumns {
+    icon: bool,
+    spend: bool,
+    spend_pct: bool,
+    budget: bool,
+    status: bool,
+}
+
+fn render_categories(
+    cli: &Cli,
+    rows: Vec<CategoryRow>,
+    columns: &CategoryColumns,
+) -> anyhow::Result<()> {
+    if cli.output == OutputFormat::Json {
+        return render_output(cli, rows);
+    }
+    render_categories_table(cli, &rows, columns, None)
+}
+
+#[derive(Debug, Serialize)]
+struct CategoriesWithTotal {
+    categories: Vec<CategoryRow>,
+    total_spend: String,
+}
+
+fn render_categories_with_total(
+    cli: &Cli,
+    rows: Vec<CategoryRow>,
+    columns: &CategoryColumns,
+    total: &str,
+) -> anyhow::Result<()> {
+    if cli.output == OutputFormat::Json {
+        let out = CategoriesWithTotal {
+            categories: rows,
+            total_spend: total.to_string(),
+        };
+        writeln!(cli.out.lock(), "{}", serde_json::to_string_pretty(&out)?)?;
+        return Ok(());
+    }
+    render_categories_table(cli, &rows, columns, Some(total))
+}
+
+fn render_categories_table(
+    cli: &Cli,
+    rows: &[CategoryRow],
+    columns: &CategoryColumns,
+    total_spend: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut headers = CategoryRow::HEADERS.to_vec();
+    if columns.spend {
+        headers.push("current_spend");
+    }
+    if columns.spend_pct {
+        headers.push("spend_pct");
+    }
+    if columns.budget {
+        headers.extend(["budget_amount", "rollover_disabled"]);
+    }
+    if columns.status {
+        headers.push("status");
+    }
+    if columns.icon {
+        headers.push("icon");
+    }
+
+    let mut table = new_table();
+    table.set_header(ComfyRow::from(
+        headers
+            .iter()
+            .map(|h| header_cell(cli, h))
+            .collect::<Vec<_>>(),
+    ));
+    for row in rows {
+        let mut cells = row.cells(cli);
+        if columns.spend {
+            cells.push(Cell::new(&row.current_spend).set_alignment(CellAlignment::Right));
+        }
+        if columns.spend_pct {
+            cells.push(
+                Cell::new(row.spend_pct.as_deref().unwrap_or_default())
+                    .set_alignment(CellAlignment::Right),
+            );
+        }
+        if columns.budget {
+            cells.push(Cell::new(&row.budget_amount).set_alignment(CellAlignment::Right));
+            cells.push(Cell::new(&row.rollover_disabled));
+        }
+        if columns.status {
+            let status = row.status.as_deref().unwrap_or_default();
+            let mut cell = Cell::new(status);
+            if super::should_color(cli) {
+                match status {
+                    "over" => cell = cell.fg(Color::Red),
+                    "warning" => cell = cell.fg(Color::Yellow),
+                    "on-track" => cell = cell.fg(Color::Green),
+                    _ => {}
+                }
+            }
+            cells.push(cell);
+        }
+        if columns.icon {
+            cells.push(Cell::new(icon_display(cli, row.icon.as_ref())));
+        }
+        table.add_row(ComfyRow::from(cells));
+    }
+    if let Some(total) = total_spend {
+        let mut cells = vec![Cell::new(""), Cell::new("TOTAL")];
+        cells.extend((2..CategoryRow::HEADERS.len()).map(|_| Cell::new("")));
+        cells.push(Cell::new(total).set_alignment(CellAlignment::Right));
+        table.add_row(ComfyRow::from(cells));
+    }
+    writeln!(cli.out.lock(), "{table}")?;
+    Ok(())
+}
+
+fn spend_by_id(categories: &[Category]) -> HashMap<CategoryId, f64> {
+    fn walk(out: &mut HashMap<CategoryId, f64>, cats: &[Category]) {
+        for c in cats {
+            if let Some(v) = c
+                .spend
+                .as_ref()
+                .and_then(|s| s.current.as_ref())
+                .and_then(|m| value_to_f64(m.amount.as_ref()))
+            {
+                out.insert(c.id.clone(), v);
+            }
+            walk(out, c.child_categories.as_deref().unwrap_or_default());
+        }
+    }
+    let mut out = HashMap::new();
+    walk(&mut out, categories);
+    out
+}
+
+/// Sums spend over the non-excluded top-level categories (a parent's spend already includes
+/// its children's).
+fn total_spend(categories: &[Category]) -> f64 {
+    categories
+        .iter()
+        .filter(|c| c.is_excluded != Some(true))
+        .filter_map(|c| {
+            c.spend
+                .as_ref()
+                .and_then(|s| s.current.as_ref())
+                .and_then(|m| value_to_f64(m.amount.as_ref()))
+        })
+        .sum()
+}
+
+/// Fills `spend_pct` for non-excluded rows with spend data. Excluded categories stay blank,
+/// since they are not part of the total they would be a share of.
+fn set_spend_percentages(rows: &mut [CategoryRow], spend: &HashMap<CategoryId, f64>, total: f64) {
+    if total == 0.0 {
+        return;
+    }
+    for row in rows.iter_mut().filter(|r| r.excluded != "true") {
+        row.spend_pct = spend
+            .get(&row.id)
+            .map(|v| format!("{:.1}%", v / total * 100.0));
+    }
 }
 
 impl TableRow for CategoryRow {
     const HEADERS: &'static [&'static str] =
         &["id", "name", "parent_id", "excluded", "can_be_deleted"];
 
-    fn cells(&self) -> Vec<Cell> {
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
         vec![
-            Cell::new(shorten_id_for_table(self.id.as_str())),
-            Cell::new(&self.name),
+            Cell::new(shorten_id_for_table(cli, self.id.as_str())),
+            // Indent children under their parent instead of adding a column.
+            Cell::new(format!(
+                "{}{}",
+                "  ".repeat(self.depth.unwrap_or(0)),
+                self.name
+            )),
             Cell::new(
                 self.parent_id
                     .as_ref()
-                    .map(|p| shorten_id_for_table(p.as_str()))
+                    .map(|p| shorten_id_for_table(cli, p.as_str()))
                     .unwrap_or_default(),
             ),
             Cell::new(&self.excluded),
@@ -158,6 +663,28 @@ impl TableRow for CategoryRow {
     }
 }
 
+fn icon_display(cli: &Cli, icon: Option<&Icon>) -> String {
+    match icon {
+        Some(Icon::EmojiUnicode { unicode: Some(u) }) => emoji_from_unicode(u),
+        Some(Icon::Genmoji { id: Some(id), .. }) => shorten_id_for_table(cli, id),
+        _ => String::new(),
+    }
+}
+
+/// Copilot may send either the emoji itself or its hex codepoint (e.g. `1F6D2` / `U+1F6D2`).
+fn emoji_from_unicode(unicode: &str) -> String {
+    let hex = unicode.trim();
+    let hex = hex
+        .strip_prefix("U+")
+        .or_else(|| hex.strip_prefix("u+"))
+        .unwrap_or(hex);
+    u32::from_str_radix(hex, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .map(|c| c.to_string())
+        .unwrap_or_else(|| unicode.to_string())
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct FlatCategory {
     id: CategoryId,
@@ -165,6 +692,89 @@ struct FlatCategory {
     parent_id: Option<CategoryId>,
     is_excluded: Option<bool>,
     can_be_deleted: Option<bool>,
+    is_rollover_disabled: Option<bool>,
+    color_name: Option<String>,
+    template_id: Option<String>,
+    current_spend: String,
+    /// Numeric current spend, for sorting.
+    spend_amount: Option<f64>,
+    budget_amount: String,
+    /// Numeric current budget, for the spend-vs-budget status.
+    budget_value: Option<f64>,
+    icon: Option<Icon>,
+    /// 0 for top-level categories, 1 for their children, and so on.
+    depth: usize,
+}
+
+fn write_categories_csv(
+    w: impl Write,
+    categories: &[FlatCategory],
+    args: &CategoriesExportArgs,
+) -> anyhow::Result<()> {
+    let mut header = vec![
+        "id",
+        "name",
+        "parent_id",
+        "is_excluded",
+        "can_be_deleted",
+        "color_name",
+        "template_id",
+    ];
+    if args.spend {
+        header.push("current_spend");
+    }
+    if args.budget {
+        header.extend(["budget_amount", "rollover_disabled"]);
+    }
+
+    let mut csv = csv::Writer::from_writer(w);
+    csv.write_record(&header)?;
+    for c in categories {
+        let mut record = vec![
+            c.id.to_string(),
+            c.name.clone(),
+            c.parent_id
+                .as_ref()
+                .map(|p| p.to_string())
+                .unwrap_or_default(),
+            c.is_excluded.unwrap_or(false).to_string(),
+            c.can_be_deleted.unwrap_or(false).to_string(),
+            c.color_name.clone().unwrap_or_default(),
+            c.template_id.clone().unwrap_or_default(),
+        ];
+        if args.spend {
+            record.push(c.current_spend.clone());
+        }
+        if args.budget {
+            record.push(c.budget_amount.clone());
+            record.push(c.is_rollover_disabled.unwrap_or(false).to_string());
+        }
+        csv.write_record(&record)?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Finds a category by id, searching child categories too.
+pub(super) fn find_category<'a>(
+    categories: &'a [Category],
+    id: &CategoryId,
+) -> Option<&'a Category> {
+    categories.iter().find_map(|c| {
+        if &c.id == id {
+            Some(c)
+        } else {
+            find_category(c.child_categories.as_deref().unwrap_or_default(), id)
+        }
+    })
+}
+
+fn current_amount(periods: Option<&CategoryPeriods>) -> String {
+    value_to_money_string(
+        periods
+            .and_then(|p| p.current.as_ref())
+            .and_then(|m| m.amount.clone()),
+    )
 }
 
 fn flatten_categories(categories: &[Category], include_children: bool) -> Vec<FlatCategory> {
@@ -172,6 +782,7 @@ fn flatten_categories(categories: &[Category], include_children: bool) -> Vec<Fl
         out: &mut Vec<FlatCategory>,
         cats: &[Category],
         parent_id: Option<&CategoryId>,
+        depth: usize,
         include_children: bool,
     ) {
         for c in cats {
@@ -181,14 +792,33 @@ fn flatten_categories(categories: &[Category], include_children: bool) -> Vec<Fl
                 parent_id: parent_id.cloned(),
                 is_excluded: c.is_excluded,
                 can_be_deleted: c.can_be_deleted,
+                is_rollover_disabled: c.is_rollover_disabled,
+                color_name: c.color_name.clone(),
+                template_id: c.template_id.clone(),
+                current_spend: current_amount(c.spend.as_ref()),
+                spend_amount: value_to_f64(
+                    c.spend
+                        .as_ref()
+                        .and_then(|p| p.current.as_ref())
+                        .and_then(|m| m.amount.as_ref()),
+                ),
+                budget_amount: current_amount(c.budget.as_ref()),
+                budget_value: value_to_f64(
+                    c.budget
+                        .as_ref()
+                        .and_then(|p| p.current.as_ref())
+                        .and_then(|m| m.amount.as_ref()),
+                ),
+                icon: c.icon.clone(),
+                depth,
             });
             if include_children && let Some(children) = c.child_categories.as_ref() {
-                walk(out, children, Some(&c.id), include_children);
+                walk(out, children, Some(&c.id), depth + 1, include_children);
             }
         }
     }
 
     let mut out = Vec::new();
-    walk(&mut out, categories, None, include_children);
+    walk(&mut out, categories, None, 0, include_children);
     out
 }
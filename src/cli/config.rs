@@ -0,0 +1,86 @@
+use std::io::Write;
+
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+
+use crate::config::{
+    config_get, config_json_schema, config_path, config_set, load_config, load_config_document,
+    save_config_document,
+};
+
+use super::{Cli, Command, ConfigCmd, TransactionsCmd};
+
+pub(super) fn run_config(cli: &Cli, cmd: ConfigCmd) -> anyhow::Result<()> {
+    let path = config_path();
+    match cmd {
+        ConfigCmd::Get { key } => {
+            let doc = load_config_document(&path)?;
+            let Some(item) = config_get(&doc, &key) else {
+                anyhow::bail!("{key} is not set in {}", path.display());
+            };
+            let value = match item.as_str() {
+                Some(s) => s.to_string(),
+                None => item.to_string().trim().to_string(),
+            };
+            writeln!(cli.out.lock(), "{value}")?;
+            Ok(())
+        }
+        ConfigCmd::Set { key, value } => {
+            let mut doc = load_config_document(&path)?;
+            config_set(&mut doc, &key, &value)?;
+            if cli.dry_run {
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would set {key} = {value} in {}",
+                    path.display()
+                )?;
+                return Ok(());
+            }
+            save_config_document(&path, &doc)?;
+            writeln!(cli.out.lock(), "set {key} = {value}")?;
+            Ok(())
+        }
+        ConfigCmd::JsonSchema => {
+            let schema = serde_json::to_string_pretty(&config_json_schema())?;
+            writeln!(cli.out.lock(), "{schema}")?;
+            Ok(())
+        }
+    }
+}
+
+/// Fills in defaults from the config file for flags that weren't given on the command line.
+///
+/// A missing config file is fine; an invalid one is reported and ignored so `copilot config set`
+/// can still be used to fix it.
+pub fn apply_config_defaults(cli: &mut Cli, matches: &ArgMatches) {
+    let config = match load_config(&config_path()) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("warning: ignoring config file: {e:#}");
+            return;
+        }
+    };
+    let is_default =
+        |m: &ArgMatches, id: &str| m.value_source(id) == Some(ValueSource::DefaultValue);
+
+    if let Some(output) = config.output
+        && is_default(matches, "output")
+    {
+        cli.output = output;
+    }
+    if let Some(color) = config.color
+        && is_default(matches, "color")
+    {
+        cli.color = color;
+    }
+    if let Some(limit) = config.transactions.list.limit
+        && let Command::Transactions { cmd } = &mut cli.command
+        && let TransactionsCmd::List(args) = cmd.as_mut()
+        && let Some(m) = matches
+            .subcommand_matches("transactions")
+            .and_then(|m| m.subcommand_matches("list"))
+        && is_default(m, "page_size")
+    {
+        args.page_size = limit;
+    }
+}
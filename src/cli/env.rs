@@ -0,0 +1,92 @@
+use comfy_table::Cell;
+use serde::Serialize;
+
+use crate::config::{session_path, token_path};
+
+use super::Cli;
+use super::render::{TableRow, render_output};
+
+/// Environment variables the CLI (or its token helper) reads, in display order.
+const ENV_VARS: &[&str] = &[
+    "COPILOT_TOKEN",
+    "COPILOT_TOKEN_FILE",
+    "COPILOT_BASE_URL",
+    "COPILOT_SESSION_DIR",
+    "COPILOT_FIXTURES_DIR",
+    "COPILOT_TOKEN_HELPER",
+    "COPILOT_HTTP_TIMEOUT_SECS",
+    "COPILOT_HTTP_CONNECT_TIMEOUT_SECS",
+    "HOME",
+    "XDG_CONFIG_HOME",
+    "COLUMNS",
+    "NO_COLOR",
+    "RUST_LOG",
+];
+
+#[derive(Debug, Clone, Serialize)]
+struct EnvRow {
+    name: String,
+    source: String,
+    value: String,
+}
+
+impl TableRow for EnvRow {
+    const HEADERS: &'static [&'static str] = &["name", "source", "value"];
+
+    fn cells(&self, _cli: &Cli) -> Vec<Cell> {
+        vec![
+            Cell::new(&self.name),
+            Cell::new(&self.source),
+            Cell::new(&self.value),
+        ]
+    }
+}
+
+/// Shows only the last 8 characters of a secret.
+fn mask_secret(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() <= 8 {
+        return "***".to_string();
+    }
+    let tail: String = chars[chars.len() - 8..].iter().collect();
+    format!("***{tail}")
+}
+
+pub(super) fn run_env(cli: &Cli) -> anyhow::Result<()> {
+    let mut rows: Vec<EnvRow> = ENV_VARS
+        .iter()
+        .map(|name| {
+            let value = std::env::var(name).ok();
+            EnvRow {
+                name: name.to_string(),
+                source: if value.is_some() { "env" } else { "unset" }.to_string(),
+                value: match value {
+                    Some(v) if *name == "COPILOT_TOKEN" => mask_secret(&v),
+                    Some(v) => v,
+                    None => String::new(),
+                },
+            }
+        })
+        .collect();
+
+    let (token_source, token_file) = match &cli.token_file {
+        Some(p) => ("override", p.clone()),
+        None => ("default", token_path()),
+    };
+    rows.push(EnvRow {
+        name: "token_path".to_string(),
+        source: token_source.to_string(),
+        value: token_file.display().to_string(),
+    });
+    let (session_source, session_dir) = match &cli.session_dir {
+        Some(p) => ("override", p.clone()),
+        None => ("default", session_path()),
+    };
+    rows.push(EnvRow {
+        name: "session_path".to_string(),
+        source: session_source.to_string(),
+        value: session_dir.display().to_string(),
+    });
+
+    render_output(cli, rows)
+}
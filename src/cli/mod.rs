@@ -1,47 +1,70 @@
 use std::collections::{HashMap, HashSet};
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
 
 use anyhow::Context;
 use clap::builder::ArgGroup;
 use clap::{Args, Parser, Subcommand, ValueEnum};
-use comfy_table::modifiers::UTF8_ROUND_CORNERS;
-use comfy_table::presets::UTF8_FULL;
-use comfy_table::{Cell, Color, ContentArrangement, Row as ComfyRow, Table};
-use serde::Serialize;
+use comfy_table::{Cell, Color, Row as ComfyRow};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::client::{
-    BulkEditTransactionsResult, Category, ClientMode, CopilotClient, PageInfo, Transaction,
-    TransactionIdRef,
+    ApiError, BulkEditTransactionsResult, Category, ClientMode, CopilotClient, CopilotClientTrait,
+    PageInfo, Transaction, TransactionIdRef,
 };
 use crate::config::{load_token, session_path, token_path};
 use crate::types::{
-    CategoryId, RecurringFrequency, RecurringId, TagId, TransactionId, TransactionType,
+    AccountId, CategoryId, RecurringFrequency, RecurringId, TagId, TransactionId, TransactionType,
 };
 
+mod accounts;
 mod auth;
+mod batch;
 mod budgets;
 mod categories;
+mod config;
+mod env;
 mod recurrings;
 mod render;
 mod tags;
+mod watch;
 use render::{
-    KeyValueRow, TableRow, header_cell, render_output, shorten_id_for_table, terminal_width,
+    KeyValueRow, OutputSink, TableRow, header_cell, new_table, render_output, render_output_to,
+    shorten_id_for_table,
 };
 
-#[derive(Debug, Clone, Copy, ValueEnum, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     Json,
     Table,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// `{"error": "...", "kind": "auth|not-found|validation|general"}` on stderr.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum ColorMode {
     Auto,
     Always,
     Never,
 }
 
+/// How to read a date written `NN-NN-YYYY`; `YYYY-MM-DD` is never ambiguous.
+#[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
+pub enum DateOrder {
+    /// Month first: `04-05-2025` is April 5.
+    #[default]
+    Mdy,
+    /// Day first: `04-05-2025` is May 4.
+    Dmy,
+}
+
 #[derive(Debug, Clone, Parser)]
 #[command(name = "copilot")]
 #[command(about = "CLI for Copilot Money (unofficial)", version)]
@@ -55,6 +78,40 @@ pub struct Cli {
     #[arg(long, value_enum, default_value_t = ColorMode::Auto, global = true)]
     pub color: ColorMode,
 
+    /// How to read dates written `NN-NN-YYYY` in date flags: month first (default) or day first.
+    #[arg(long, value_enum, default_value_t = DateOrder::Mdy, global = true)]
+    pub date_order: DateOrder,
+
+    /// Default `--date-from` for commands that support a date range (currently
+    /// `transactions list`/`search` and `budgets month`).
+    #[arg(long, global = true)]
+    pub global_date_from: Option<String>,
+
+    /// Default `--date-to` for commands that support a date range.
+    #[arg(long, global = true)]
+    pub global_date_to: Option<String>,
+
+    /// Show full IDs in table output instead of shortening long ones.
+    #[arg(long, global = true, default_value_t = false)]
+    pub no_truncate_ids: bool,
+
+    /// Write command output to this file instead of stdout (`-` means stdout).
+    #[arg(long, global = true, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+
+    /// Like `--output-file`, but append to the file (creating it if needed) instead of replacing it.
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        conflicts_with = "output_file"
+    )]
+    pub append_output_file: Option<PathBuf>,
+
+    /// Print errors in a machine-readable format on stderr (default: plain text).
+    #[arg(long, value_enum, global = true)]
+    pub format_errors: Option<ErrorFormat>,
+
     #[arg(long, global = true)]
     pub dry_run: bool,
 
@@ -79,8 +136,32 @@ pub struct Cli {
     #[arg(long, global = true, env = "COPILOT_SESSION_DIR")]
     pub session_dir: Option<PathBuf>,
 
+    /// Retry a request this many times after a network error or 5xx response, with
+    /// exponential backoff. 4xx responses and GraphQL errors are never retried; mutations are
+    /// only retried when the connection itself failed.
+    #[arg(long, global = true, default_value_t = 0)]
+    pub max_retries: u32,
+
+    /// Base delay before the first retry; each later retry doubles it.
+    #[arg(long, global = true, default_value_t = 1000, value_name = "MS")]
+    pub retry_delay_ms: u64,
+
+    /// Timeout for a whole HTTP request, including waiting for the response
+    /// (default: `COPILOT_HTTP_TIMEOUT_SECS`, else 60).
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub request_timeout_seconds: Option<u64>,
+
+    /// Timeout for establishing the connection only, so network problems fail fast while
+    /// slow responses still get the full request timeout
+    /// (default: `COPILOT_HTTP_CONNECT_TIMEOUT_SECS`, else 10).
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub connection_timeout_seconds: Option<u64>,
+
     #[arg(long, global = true, env = "COPILOT_FIXTURES_DIR", hide = true)]
     pub fixtures_dir: Option<PathBuf>,
+
+    #[arg(skip)]
+    pub(crate) out: OutputSink,
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -90,8 +171,9 @@ pub enum Command {
         cmd: AuthCmd,
     },
     Transactions {
+        // Boxed: the list filters make this variant far larger than the rest.
         #[command(subcommand)]
-        cmd: TransactionsCmd,
+        cmd: Box<TransactionsCmd>,
     },
     Categories {
         #[command(subcommand)]
@@ -105,13 +187,80 @@ pub enum Command {
         #[command(subcommand)]
         cmd: TagsCmd,
     },
+    Accounts {
+        #[command(subcommand)]
+        cmd: AccountsCmd,
+    },
     Budgets {
         #[command(subcommand)]
         cmd: BudgetsCmd,
     },
+    /// Poll for new transactions and print them as they arrive (Ctrl-C to stop).
+    Watch(WatchArgs),
+    /// Run commands from a JSONL file, one `{"command": [...]}` per line (requires `--yes`).
+    Batch(BatchArgs),
+    /// Print the environment variables and config paths the CLI resolves (for debugging).
+    Env,
+    /// Read or update defaults in `~/.config/copilot-money-cli/config.toml`.
+    Config {
+        #[command(subcommand)]
+        cmd: ConfigCmd,
+    },
+    /// Print a shell completion script.
+    #[command(after_help = "Install:\n  \
+        bash: eval \"$(copilot completions bash)\"  (add to ~/.bashrc)\n  \
+        zsh:  eval \"$(copilot completions zsh)\"  (add to ~/.zshrc)\n  \
+        fish: copilot completions fish > ~/.config/fish/completions/copilot.fish")]
+    Completions {
+        shell: clap_complete::Shell,
+    },
     Version,
 }
 
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigCmd {
+    /// Print one value by dotted key (e.g. `transactions.list.limit`).
+    Get { key: String },
+    /// Set one value by dotted key, keeping the rest of the file (and its comments) intact.
+    Set { key: String, value: String },
+    /// Print the JSON Schema for the config file (for editor completion and validation).
+    JsonSchema,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BatchArgs {
+    pub file: PathBuf,
+
+    /// Stop at the first failing line instead of continuing.
+    #[arg(long, default_value_t = false)]
+    pub fail_fast: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct WatchArgs {
+    /// Seconds between polls.
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    /// Columns to show in table output (comma-separated).
+    #[arg(
+        long,
+        value_parser = TransactionFieldParser,
+        value_delimiter = ',',
+        default_value = "date,name,amount,reviewed,category,tags,type"
+    )]
+    pub fields: Vec<TransactionField>,
+
+    /// Poll once and exit (for cron). The newest transaction seen is saved to `--state-file`,
+    /// so the next run prints only what arrived since; the very first run just records it.
+    #[arg(long, default_value_t = false)]
+    pub run_once: bool,
+
+    /// Where `--run-once` keeps its marker (default: `~/.config/copilot-money-cli/watch-state.json`).
+    #[arg(long, requires = "run_once")]
+    pub state_file: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Subcommand)]
 pub enum AuthCmd {
     Status,
@@ -143,7 +292,8 @@ pub struct AuthLoginArgs {
     #[arg(long)]
     pub email: Option<String>,
 
-    #[arg(long, default_value_t = 180)]
+    /// How long the browser helper waits for the login to finish.
+    #[arg(long, default_value_t = 180, value_parser = clap::value_parser!(u64).range(1..))]
     pub timeout_seconds: u64,
 
     /// Store a persistent browser session so tokens can be refreshed automatically.
@@ -155,11 +305,15 @@ pub struct AuthLoginArgs {
     /// Do not store a persistent browser session (tokens may expire and require re-auth).
     #[arg(long, default_value_t = false)]
     pub no_persist_session: bool,
+
+    /// Print the token to stdout instead of saving it, e.g. `COPILOT_TOKEN=$(copilot auth login --no-save)`.
+    #[arg(long, default_value_t = false)]
+    pub no_save: bool,
 }
 
 #[derive(Debug, Clone, Args)]
 pub struct AuthRefreshArgs {
-    #[arg(long, default_value_t = 180)]
+    #[arg(long, default_value_t = 180, value_parser = clap::value_parser!(u64).range(1..))]
     pub timeout_seconds: u64,
 }
 
@@ -177,6 +331,8 @@ pub enum TransactionsCmd {
     Show(TransactionsShowArgs),
     Review(TransactionsReviewArgs),
     Unreview(TransactionsReviewArgs),
+    /// Mark every transaction matching the `list` filters reviewed, in a single API call.
+    BulkReview(TransactionsBulkReviewArgs),
     SetCategory(TransactionsSetCategoryArgs),
     AssignRecurring(TransactionsAssignRecurringArgs),
     SetNotes(TransactionsSetNotesArgs),
@@ -209,22 +365,120 @@ pub enum TransactionField {
     Name,
     Amount,
     Reviewed,
+    Pending,
     Category,
     Tags,
     Type,
     Id,
+    Notes,
+    #[value(alias = "account")]
+    AccountId,
+    RecurringId,
+    /// Shorthand for every column above.
+    All,
+}
+
+impl TransactionField {
+    const ALL: &'static [TransactionField] = &[
+        TransactionField::Date,
+        TransactionField::Name,
+        TransactionField::Amount,
+        TransactionField::Reviewed,
+        TransactionField::Pending,
+        TransactionField::Category,
+        TransactionField::Tags,
+        TransactionField::Type,
+        TransactionField::Id,
+        TransactionField::Notes,
+        TransactionField::AccountId,
+        TransactionField::RecurringId,
+    ];
+}
+
+/// Parses a `--fields` / `--client-sort` name, listing every valid name on error (clap's
+/// default message only says the value is invalid). Possible values still come from
+/// `ValueEnum`, so help and shell completions are unchanged.
+#[derive(Debug, Clone, Copy)]
+struct TransactionFieldParser;
+
+impl clap::builder::TypedValueParser for TransactionFieldParser {
+    type Value = TransactionField;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<TransactionField, clap::Error> {
+        parse_transaction_field(&value.to_string_lossy()).map_err(|msg| {
+            clap::Error::raw(clap::error::ErrorKind::InvalidValue, format!("{msg}\n")).with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
+        Some(Box::new(
+            TransactionField::value_variants()
+                .iter()
+                .filter_map(|f| f.to_possible_value()),
+        ))
+    }
+}
+
+fn parse_transaction_field(s: &str) -> Result<TransactionField, String> {
+    TransactionField::from_str(s.trim(), true).map_err(|_| {
+        let valid = TransactionField::value_variants()
+            .iter()
+            .filter_map(|f| f.to_possible_value())
+            .map(|v| v.get_name().to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("unknown field '{s}'; valid fields are: {valid}")
+    })
+}
+
+/// Expands `--fields all` into every concrete column.
+fn expand_fields(fields: &[TransactionField]) -> Vec<TransactionField> {
+    if fields.contains(&TransactionField::All) {
+        TransactionField::ALL.to_vec()
+    } else {
+        fields.to_vec()
+    }
 }
 
 #[derive(Debug, Clone, Args)]
 pub struct TransactionsListArgs {
-    #[arg(long, default_value_t = 25)]
-    pub limit: usize,
+    /// Transactions per page (not a total cap; see `--pages`). `0` is treated as `--all`.
+    ///
+    /// `--limit` is kept as an alias for backward compatibility.
+    #[arg(long, visible_alias = "limit", default_value_t = 25)]
+    pub page_size: usize,
 
     /// Cursor to continue pagination from a previous call (`pageInfo.endCursor`).
     #[arg(long)]
     pub after: Option<String>,
 
-    /// Number of pages to fetch (each page is `--limit`).
+    /// Start right after this transaction (a bookmark that, unlike `--after`, stays valid
+    /// across versions).
+    ///
+    /// Finding it means paging from the start until it shows up, so this costs one request
+    /// per page before it.
+    #[arg(long, value_name = "TXN_ID", conflicts_with = "after")]
+    pub after_id: Option<TransactionId>,
+
+    /// Fetch only transactions newer than this saved cursor, for incremental sync.
+    ///
+    /// The cursor is `YYYY-MM-DD` (start with everything from that day) or the
+    /// `YYYY-MM-DD:<id>,...` value a previous run printed to stderr as `since-cursor: ...`.
+    #[arg(
+        long,
+        value_name = "CURSOR",
+        conflicts_with_all = ["after", "after_id", "pages", "all", "skip_pages", "sort"]
+    )]
+    pub since_cursor: Option<String>,
+
+    /// Number of pages to fetch (each page is `--page-size`).
     #[arg(long, default_value_t = 1)]
     pub pages: usize,
 
@@ -232,6 +486,12 @@ pub struct TransactionsListArgs {
     #[arg(long, default_value_t = false, conflicts_with = "pages")]
     pub all: bool,
 
+    /// Fetch and discard this many pages first (`--skip-pages 3 --pages 1` shows page 4).
+    ///
+    /// Each skipped page is still a full request, so large values are slow.
+    #[arg(long, default_value_t = 0)]
+    pub skip_pages: usize,
+
     /// Filter to reviewed transactions only.
     #[arg(long, default_value_t = false, conflicts_with = "unreviewed")]
     pub reviewed: bool,
@@ -248,18 +508,97 @@ pub struct TransactionsListArgs {
     #[arg(long, conflicts_with = "category_id")]
     pub category: Option<String>,
 
-    /// Filter to transactions that include any of these tags (repeatable).
+    /// Hide transactions in this category id (repeatable; hides every listed one).
+    #[arg(long, value_name = "CATEGORY_ID")]
+    pub exclude_category_id: Vec<CategoryId>,
+
+    /// Hide transactions in this category, by name (repeatable; case-insensitive exact match).
+    #[arg(long, value_name = "NAME")]
+    pub exclude_category: Vec<String>,
+
+    /// Hide transactions of this type (repeatable).
+    #[arg(long, value_enum, value_name = "TYPE")]
+    pub exclude_type: Vec<TransactionType>,
+
+    /// Hide internal transfers between your own accounts (same as `--exclude-type internal-transfer`).
+    #[arg(long, default_value_t = false)]
+    pub hide_internal_transfers: bool,
+
+    /// Filter to transactions that include any of these tags, by name (repeatable).
     #[arg(long, value_name = "TAG")]
     pub tag: Vec<String>,
 
+    /// Filter to transactions that include any of these tags, by id (repeatable).
+    ///
+    /// Unlike `--tag`, ids are stable across renames. Combined with `--tag`, a transaction
+    /// matches if either its tag names or its tag ids match.
+    #[arg(long = "tag-id", value_name = "TAG_ID")]
+    pub tag_ids: Vec<TagId>,
+
     /// Filter to a specific date (supports YYYY-MM-DD and MM-DD-YYYY).
     #[arg(long)]
     pub date: Option<String>,
 
-    /// Filter by merchant/name substring (case-insensitive).
+    /// Only include transactions on or after this date (overrides `--global-date-from`).
     #[arg(long)]
+    pub date_from: Option<String>,
+
+    /// Only include transactions on or before this date (overrides `--global-date-to`).
+    #[arg(long)]
+    pub date_to: Option<String>,
+
+    /// Filter by merchant/name substring (case-insensitive unless `--case-sensitive`).
+    ///
+    /// `--merchant` is an alias for `--name-contains`.
+    #[arg(long, visible_alias = "merchant")]
     pub name_contains: Option<String>,
 
+    /// Match `--name-contains` case-sensitively.
+    #[arg(long, default_value_t = false, requires = "name_contains")]
+    pub case_sensitive: bool,
+
+    /// Only transactions linked to a recurring.
+    #[arg(long, default_value_t = false, conflicts_with = "no_recurring")]
+    pub has_recurring: bool,
+
+    /// Only transactions not linked to any recurring (e.g. to find ones still to assign).
+    #[arg(long, default_value_t = false)]
+    pub no_recurring: bool,
+
+    /// Only money coming in (amount > 0: income, refunds, credits).
+    #[arg(long, default_value_t = false, conflicts_with = "amount_negative")]
+    pub amount_positive: bool,
+
+    /// Only money going out (amount < 0: purchases, debits).
+    #[arg(long, default_value_t = false)]
+    pub amount_negative: bool,
+
+    /// Only amounts >= this (signed, as shown: money out is negative).
+    #[arg(
+        long,
+        value_name = "AMOUNT",
+        allow_hyphen_values = true,
+        conflicts_with_all = ["amount_positive", "amount_negative"]
+    )]
+    pub amount_min: Option<f64>,
+
+    /// Only amounts <= this (signed, as shown: money out is negative).
+    #[arg(
+        long,
+        value_name = "AMOUNT",
+        allow_hyphen_values = true,
+        conflicts_with_all = ["amount_positive", "amount_negative"]
+    )]
+    pub amount_max: Option<f64>,
+
+    /// Only transactions that are still pending.
+    #[arg(long, default_value_t = false, conflicts_with = "exclude_pending")]
+    pub pending_only: bool,
+
+    /// Only posted transactions (hide pending ones).
+    #[arg(long, default_value_t = false)]
+    pub exclude_pending: bool,
+
     /// Sort transactions server-side (best-effort).
     #[arg(long, value_enum)]
     pub sort: Option<TransactionsSort>,
@@ -267,7 +606,7 @@ pub struct TransactionsListArgs {
     /// Columns to show in table output (comma-separated).
     #[arg(
         long,
-        value_enum,
+        value_parser = TransactionFieldParser,
         value_delimiter = ',',
         default_value = "date,name,amount,reviewed,category,tags,type"
     )]
@@ -276,12 +615,27 @@ pub struct TransactionsListArgs {
     /// Include pagination info (`pageInfo`) in the output.
     #[arg(long, default_value_t = false)]
     pub page_info: bool,
+
+    /// Sort the fetched transactions client-side by any field (ascending).
+    ///
+    /// Only the fetched window is sorted; use `--sort` for server-side date/amount ordering.
+    #[arg(long, value_parser = TransactionFieldParser, value_name = "FIELD")]
+    pub client_sort: Option<TransactionField>,
+
+    /// Reverse `--client-sort` (descending).
+    #[arg(long, default_value_t = false, requires = "client_sort")]
+    pub client_sort_desc: bool,
 }
 
 #[derive(Debug, Clone, Args)]
 pub struct TransactionsSearchArgs {
+    /// Merchant/name substring (case-insensitive unless `--case-sensitive`).
     pub query: String,
 
+    /// Match the query case-sensitively.
+    #[arg(long, default_value_t = false)]
+    pub case_sensitive: bool,
+
     #[arg(long, default_value_t = 200)]
     pub limit: usize,
 
@@ -321,6 +675,14 @@ pub struct TransactionsSearchArgs {
     #[arg(long)]
     pub date: Option<String>,
 
+    /// Only include transactions on or after this date (overrides `--global-date-from`).
+    #[arg(long)]
+    pub date_from: Option<String>,
+
+    /// Only include transactions on or before this date (overrides `--global-date-to`).
+    #[arg(long)]
+    pub date_to: Option<String>,
+
     /// Sort transactions server-side (best-effort).
     #[arg(long, value_enum)]
     pub sort: Option<TransactionsSort>,
@@ -328,7 +690,7 @@ pub struct TransactionsSearchArgs {
     /// Columns to show in table output (comma-separated).
     #[arg(
         long,
-        value_enum,
+        value_parser = TransactionFieldParser,
         value_delimiter = ',',
         default_value = "date,name,amount,reviewed,category,tags,type"
     )]
@@ -352,6 +714,61 @@ pub struct TransactionsReviewArgs {
     pub ids: Vec<TransactionId>,
 }
 
+#[derive(Debug, Clone, Args)]
+pub struct TransactionsBulkReviewArgs {
+    /// Only transactions not yet reviewed.
+    #[arg(long, default_value_t = false)]
+    pub unreviewed: bool,
+
+    /// Only transactions in this category id.
+    #[arg(long)]
+    pub category_id: Option<CategoryId>,
+
+    /// Only transactions in this category, by name (case-insensitive exact match).
+    #[arg(long, conflicts_with = "category_id")]
+    pub category: Option<String>,
+
+    /// Skip transactions of this type (repeatable).
+    #[arg(long, value_enum, value_name = "TYPE")]
+    pub exclude_type: Vec<TransactionType>,
+
+    /// Only transactions with any of these tags, by name (repeatable).
+    #[arg(long, value_name = "TAG")]
+    pub tag: Vec<String>,
+
+    /// Only transactions with any of these tags, by id (repeatable; OR-combined with `--tag`).
+    #[arg(long = "tag-id", value_name = "TAG_ID")]
+    pub tag_ids: Vec<TagId>,
+
+    /// Only transactions on this date (supports YYYY-MM-DD and MM-DD-YYYY).
+    #[arg(long)]
+    pub date: Option<String>,
+
+    /// Only transactions on or after this date (overrides `--global-date-from`).
+    #[arg(long)]
+    pub date_from: Option<String>,
+
+    /// Only transactions on or before this date (overrides `--global-date-to`).
+    #[arg(long)]
+    pub date_to: Option<String>,
+
+    /// Only transactions whose merchant/name contains this (case-insensitive unless `--case-sensitive`).
+    #[arg(long, visible_alias = "merchant")]
+    pub name_contains: Option<String>,
+
+    /// Match `--name-contains` case-sensitively.
+    #[arg(long, default_value_t = false, requires = "name_contains")]
+    pub case_sensitive: bool,
+
+    /// Only amounts >= this (signed, as shown: money out is negative).
+    #[arg(long, value_name = "AMOUNT", allow_hyphen_values = true)]
+    pub amount_min: Option<f64>,
+
+    /// Only amounts <= this (signed, as shown: money out is negative).
+    #[arg(long, value_name = "AMOUNT", allow_hyphen_values = true)]
+    pub amount_max: Option<f64>,
+}
+
 #[derive(Debug, Clone, Args)]
 #[command(group(
     ArgGroup::new("category_target")
@@ -359,6 +776,8 @@ pub struct TransactionsReviewArgs {
         .args(["category_id", "category"])
 ))]
 pub struct TransactionsSetCategoryArgs {
+    // No clap `conflicts_with` here: it stops clap from enforcing the filters' `requires`.
+    #[arg(required_unless_present = "all_matching")]
     pub ids: Vec<TransactionId>,
 
     #[arg(long)]
@@ -366,6 +785,50 @@ pub struct TransactionsSetCategoryArgs {
 
     #[arg(long)]
     pub category: Option<String>,
+
+    /// Instead of ids, update every transaction matching the filters below (all pages; requires `--yes`).
+    #[arg(long, default_value_t = false)]
+    pub all_matching: bool,
+
+    #[command(flatten)]
+    pub filters: TransactionMatchArgs,
+}
+
+// Filters for `--all-matching` bulk edits (a `///` doc here would become the command's about).
+#[derive(Debug, Clone, Args)]
+pub struct TransactionMatchArgs {
+    /// With `--all-matching`, only reviewed transactions.
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "all_matching",
+        conflicts_with = "unreviewed"
+    )]
+    pub reviewed: bool,
+
+    /// With `--all-matching`, only unreviewed transactions.
+    #[arg(long, default_value_t = false, requires = "all_matching")]
+    pub unreviewed: bool,
+
+    /// With `--all-matching`, only transactions with any of these tags, by name (repeatable).
+    #[arg(long, value_name = "TAG", requires = "all_matching")]
+    pub tag: Vec<String>,
+
+    /// With `--all-matching`, only transactions on this date.
+    #[arg(long, requires = "all_matching")]
+    pub date: Option<String>,
+
+    /// With `--all-matching`, only transactions on or after this date.
+    #[arg(long, requires = "all_matching")]
+    pub date_from: Option<String>,
+
+    /// With `--all-matching`, only transactions on or before this date.
+    #[arg(long, requires = "all_matching")]
+    pub date_to: Option<String>,
+
+    /// With `--all-matching`, only transactions whose merchant/name contains this (case-insensitive).
+    #[arg(long, requires = "all_matching")]
+    pub name_contains: Option<String>,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -385,6 +848,40 @@ pub struct TransactionsSetNotesArgs {
 
     #[arg(long, default_value_t = false)]
     pub clear: bool,
+
+    /// Append `--notes` to the existing notes (separated by a space) instead of replacing them.
+    ///
+    /// Current notes are read before writing, so a concurrent edit in between can be lost.
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "notes",
+        conflicts_with = "clear"
+    )]
+    pub append: bool,
+
+    /// Prepend `--notes` to the existing notes (separated by a space); same caveat as `--append`.
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "notes",
+        conflicts_with_all = ["clear", "append"]
+    )]
+    pub prepend: bool,
+}
+
+impl TransactionsSetNotesArgs {
+    fn mode_label(&self) -> &'static str {
+        if self.clear {
+            "clear"
+        } else if self.append {
+            "append"
+        } else if self.prepend {
+            "prepend"
+        } else {
+            "set"
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
@@ -404,6 +901,14 @@ pub struct TransactionsSetTagsArgs {
     /// One or more tag IDs (repeatable).
     #[arg(long = "tag-id", value_name = "TAG_ID")]
     pub tag_ids: Vec<crate::types::TagId>,
+
+    /// Add the tag with this name (case-insensitive, repeatable), on top of `--mode`/`--tag-id`.
+    #[arg(long = "add-name", value_name = "NAME")]
+    pub add_names: Vec<String>,
+
+    /// Remove the tag with this name (case-insensitive, repeatable), on top of `--mode`/`--tag-id`.
+    #[arg(long = "remove-name", value_name = "NAME")]
+    pub remove_names: Vec<String>,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -427,9 +932,22 @@ pub struct TransactionsEditArgs {
 #[derive(Debug, Clone, Subcommand)]
 pub enum CategoriesCmd {
     List(CategoriesListArgs),
-    Show { id: CategoryId },
+    Show(CategoriesShowArgs),
     Create(CategoriesCreateArgs),
     Edit(CategoriesEditArgs),
+    /// Export categories (including child categories) to CSV.
+    Export(CategoriesExportArgs),
+    /// Move every transaction from one category to another.
+    Merge(CategoriesMergeArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CategoriesMergeArgs {
+    /// Category whose transactions are moved.
+    pub source_id: CategoryId,
+
+    /// Category they are moved to.
+    pub target_id: CategoryId,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -453,6 +971,101 @@ pub struct CategoriesListArgs {
     /// Filter by name substring (case-insensitive).
     #[arg(long)]
     pub name_contains: Option<String>,
+
+    /// Show an `icon` column (emoji, or a shortened Genmoji id).
+    #[arg(long, default_value_t = false)]
+    pub with_icons: bool,
+
+    /// With `--spend`, add a TOTAL row (JSON: `total_spend`) summing all non-excluded categories (unaffected by filters).
+    #[arg(long, default_value_t = false, requires = "spend")]
+    pub total: bool,
+
+    /// With `--spend`, add a `spend_pct` column: each category's share of the non-excluded total.
+    #[arg(long, default_value_t = false, requires = "spend")]
+    pub with_spend_percentage: bool,
+
+    /// Only categories excluded from budgets/spend totals.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["included_only", "excluded"])]
+    pub excluded_only: bool,
+
+    /// Only categories that are not excluded.
+    #[arg(long, default_value_t = false, conflicts_with = "excluded")]
+    pub included_only: bool,
+
+    /// Filter by excluded state (`--excluded true` is the same as `--excluded-only`).
+    #[arg(long)]
+    pub excluded: Option<bool>,
+
+    /// Print only the number of matching categories (JSON: `{"count": N}`).
+    #[arg(long = "count", default_value_t = false, conflicts_with = "total")]
+    pub count_only: bool,
+
+    /// With `--count`, print just the number.
+    #[arg(long, short = 'q', default_value_t = false, requires = "count_only")]
+    pub quiet: bool,
+
+    /// Sort categories (default: API order).
+    #[arg(long, value_enum)]
+    pub sort: Option<CategorySort>,
+
+    /// Only categories created from this template (exact template id).
+    #[arg(long = "template-id", conflicts_with = "no_template_id")]
+    pub template_id_filter: Option<String>,
+
+    /// Only custom categories (no template id).
+    #[arg(long, default_value_t = false)]
+    pub no_template_id: bool,
+
+    /// Only categories the API reports as deletable.
+    #[arg(long, default_value_t = false, conflicts_with = "cannot_delete_only")]
+    pub can_delete_only: bool,
+
+    /// Only categories that can't be deleted (including those without a `canBeDeleted` flag).
+    #[arg(long, default_value_t = false)]
+    pub cannot_delete_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum CategorySort {
+    Name,
+    /// Highest current spend first; requires `--spend`.
+    Spend,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CategoriesExportArgs {
+    /// CSV file to write (`-` for stdout).
+    // Not `output_file`: that id belongs to the global `--output-file` flag.
+    pub file: PathBuf,
+
+    /// Add a `current_spend` column.
+    #[arg(long, default_value_t = false)]
+    pub spend: bool,
+
+    /// Add `budget_amount` / `rollover_disabled` columns.
+    #[arg(long, default_value_t = false)]
+    pub budget: bool,
+
+    /// Filter by name substring (case-insensitive).
+    #[arg(long)]
+    pub name_contains: Option<String>,
+
+    /// Only children of this category.
+    #[arg(long)]
+    pub parent_id: Option<CategoryId>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CategoriesShowArgs {
+    pub id: CategoryId,
+
+    /// Include the current month's spend (`current_spend`).
+    #[arg(long, default_value_t = false)]
+    pub spend: bool,
+
+    /// Include the current month's budget (`budget_amount`) and `rollover_disabled`.
+    #[arg(long, default_value_t = false)]
+    pub budget: bool,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -474,6 +1087,18 @@ pub struct CategoriesCreateArgs {
     /// When set, include an initial budget in the category input.
     #[arg(long)]
     pub budget_unassigned_amount: Option<i64>,
+
+    /// Create as a subcategory of this category id.
+    #[arg(long, conflicts_with = "parent")]
+    pub parent_id: Option<CategoryId>,
+
+    /// Create as a subcategory of this category (by exact name).
+    #[arg(long, value_name = "NAME")]
+    pub parent: Option<String>,
+
+    /// Show each field of the input that would be sent, without creating anything.
+    #[arg(long, default_value_t = false)]
+    pub dry_run_preview: bool,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -493,19 +1118,68 @@ pub struct CategoriesEditArgs {
     pub excluded: Option<bool>,
 }
 
+#[derive(Debug, Clone, Args)]
+pub struct RecurringsShowArgs {
+    pub id: RecurringId,
+
+    /// Also list transactions attached to this recurring, scanning the first N pages
+    /// (200 transactions each; N defaults to 10).
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+    pub with_transactions: Option<usize>,
+}
+
 #[derive(Debug, Clone, Subcommand)]
 pub enum RecurringsCmd {
     List(RecurringsListArgs),
-    Show { id: RecurringId },
+    Show(RecurringsShowArgs),
     Create(RecurringsCreateArgs),
+    /// Create several recurrings from a CSV (`transaction_id,frequency,name`; requires `--yes`).
+    CreateBatch(RecurringsCreateBatchArgs),
     Edit(RecurringsEditArgs),
 }
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum TagsCmd {
-    List,
+    List(TagsListArgs),
     Create(TagsCreateArgs),
     Delete(TagsDeleteArgs),
+    /// Delete several tags, continuing past failures and reporting each one.
+    BulkDelete(TagsBulkDeleteArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct TagsListArgs {
+    /// Add a `count` column with how many fetched transactions use each tag (sorted by count).
+    ///
+    /// Scans the most recent 1000 transactions (or all with `--all`), so counts are approximate.
+    #[arg(long, default_value_t = false)]
+    pub with_counts: bool,
+
+    /// With `--with-counts`, scan all transactions instead of the most recent 1000 (can be slow).
+    #[arg(long, default_value_t = false, requires = "with_counts")]
+    pub all: bool,
+
+    /// Sort tags (default: by name, or by count with `--with-counts`).
+    #[arg(long, value_enum)]
+    pub sort: Option<TagSort>,
+
+    /// Only tags with this color (e.g. `RED1`; case-insensitive).
+    #[arg(long = "color-name", conflicts_with = "no_color_name")]
+    pub color_name_filter: Option<String>,
+
+    /// Only tags with no color set.
+    #[arg(long, default_value_t = false)]
+    pub no_color_name: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum TagSort {
+    Name,
+    Color,
+    /// Most-used first; requires `--with-counts`.
+    Count,
+    /// Keep the order the API returns (for scripts that depend on it).
+    ApiOrder,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -514,11 +1188,26 @@ pub struct TagsCreateArgs {
 
     #[arg(long)]
     pub color_name: Option<String>,
+
+    /// If a tag with this name already exists (case-insensitive), show it instead of creating one.
+    #[arg(long, default_value_t = false)]
+    pub if_not_exists: bool,
 }
 
 #[derive(Debug, Clone, Args)]
+#[command(group(ArgGroup::new("tag_target").required(true).args(["id", "name"])))]
 pub struct TagsDeleteArgs {
-    pub id: crate::types::TagId,
+    pub id: Option<crate::types::TagId>,
+
+    /// Delete the tag with this name instead (case-insensitive; fails if several match).
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct TagsBulkDeleteArgs {
+    #[arg(required = true)]
+    pub ids: Vec<crate::types::TagId>,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -527,9 +1216,58 @@ pub struct RecurringsListArgs {
     #[arg(long)]
     pub category_id: Option<CategoryId>,
 
+    /// Filter to a category by name (case-insensitive exact match).
+    #[arg(long, conflicts_with = "category_id")]
+    pub category: Option<String>,
+
+    /// Only show recurrings without a category (useful for initial setup audits).
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["category_id", "category", "has_category"]
+    )]
+    pub no_category: bool,
+
+    /// Only show recurrings that have a category.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["category_id", "category"])]
+    pub has_category: bool,
+
     /// Filter by name substring (case-insensitive).
-    #[arg(long)]
+    #[arg(long, conflicts_with = "name_exact")]
     pub name_contains: Option<String>,
+
+    /// Filter by exact name (case-insensitive), so `Rent` doesn't also match `Rent Insurance`.
+    #[arg(long)]
+    pub name_exact: Option<String>,
+
+    /// Hide recurrings with this frequency (e.g. `--frequency-not monthly`).
+    #[arg(long, value_enum)]
+    pub frequency_not: Option<RecurringFrequency>,
+
+    /// Show the rule's `min_amount` / `max_amount` columns.
+    #[arg(long, default_value_t = false)]
+    pub with_amount: bool,
+
+    /// Show a `last_seen_date` column from the most recent page of transactions
+    /// (one extra API call; older matches won't be found).
+    #[arg(long, default_value_t = false)]
+    pub with_last_seen: bool,
+
+    /// Sort recurrings (default: API order).
+    #[arg(long, value_enum)]
+    pub sort: Option<RecurringSort>,
+
+    /// Reverse the `--sort` order.
+    #[arg(long, default_value_t = false, requires = "sort")]
+    pub sort_desc: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum RecurringSort {
+    Name,
+    /// Alphabetic by frequency name (ANNUALLY, ..., WEEKLY).
+    Frequency,
+    CategoryId,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -540,6 +1278,16 @@ pub struct RecurringsCreateArgs {
     /// Recurring frequency (best-effort; Copilot expects values like ANNUALLY, MONTHLY, etc).
     #[arg(long)]
     pub frequency: RecurringFrequency,
+
+    /// Name for the recurring (default: inferred by the server from the transaction).
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct RecurringsCreateBatchArgs {
+    /// CSV with a `transaction_id,frequency,name` header; `name` may be empty.
+    pub file: PathBuf,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -557,20 +1305,95 @@ pub struct RecurringsEditArgs {
 
     #[arg(long, default_value_t = false)]
     pub recalculate_only_for_future: bool,
+
+    /// Raw JSON object to send as the input's `rule` (advanced).
+    #[arg(long, conflicts_with_all = ["name_contains", "min_amount", "max_amount"])]
+    pub rule_json: Option<String>,
+
+    /// Raw JSON to pass as the whole EditRecurring input (advanced).
+    #[arg(
+        long,
+        conflicts_with_all = ["name_contains", "min_amount", "max_amount", "recalculate_only_for_future", "rule_json"]
+    )]
+    pub input_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AccountsCmd {
+    /// List linked accounts.
+    List,
+    Show(AccountsShowArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct AccountsShowArgs {
+    pub id: AccountId,
 }
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum BudgetsCmd {
+    /// Per-category spend vs. budget for the current month.
+    List(BudgetsListArgs),
     Month,
+    /// Month-by-month budget vs. spend for one category.
+    History(BudgetsHistoryArgs),
     Set,
+    /// Set budgets for many categories from a CSV (`category_id` or `category_name`, `amount`).
+    SetFromCsv(BudgetsSetFromCsvArgs),
+    /// Enable or disable budget rollovers for a category (not implemented yet; `--dry-run` only).
+    #[command(name = "rollover")]
+    SetRollover(BudgetsSetRolloverArgs),
 }
 
-pub fn run(cli: Cli) -> anyhow::Result<()> {
-    if let Command::Version = &cli.command {
-        println!("copilot-money-cli {}", env!("CARGO_PKG_VERSION"));
-        return Ok(());
-    }
+#[derive(Debug, Clone, Args)]
+#[command(group(
+    ArgGroup::new("rollover_state")
+        .required(true)
+        .args(["enable", "disable"])
+))]
+pub struct BudgetsSetRolloverArgs {
+    pub category_id: CategoryId,
+
+    #[arg(long, default_value_t = false)]
+    pub enable: bool,
+
+    #[arg(long, default_value_t = false)]
+    pub disable: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BudgetsSetFromCsvArgs {
+    /// CSV with an `amount` column and a `category_id` or `category_name` column.
+    pub file: PathBuf,
+
+    /// Budget month (`YYYY-MM`; default: the API's current month).
+    #[arg(long)]
+    pub month: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BudgetsHistoryArgs {
+    pub category_id: CategoryId,
+
+    /// Show at most this many of the most recent months.
+    #[arg(long, default_value_t = 12)]
+    pub months: usize,
+}
 
+#[derive(Debug, Clone, Args)]
+pub struct BudgetsListArgs {
+    /// Only categories whose spend exceeds their budget (zero budgets are skipped).
+    #[arg(long, default_value_t = false, conflicts_with = "under_budget")]
+    pub over_budget: bool,
+
+    /// Only categories whose spend is below their budget (zero budgets are skipped).
+    #[arg(long, default_value_t = false)]
+    pub under_budget: bool,
+}
+
+pub use config::apply_config_defaults;
+
+pub fn run(cli: Cli) -> anyhow::Result<()> {
     let token_file_path = cli.token_file.clone().unwrap_or_else(token_path);
     let token = cli
         .token
@@ -587,25 +1410,192 @@ pub fn run(cli: Cli) -> anyhow::Result<()> {
                 .session_dir
                 .clone()
                 .or_else(|| session_path().exists().then_some(session_path())),
+            max_retries: cli.max_retries,
+            retry_delay: std::time::Duration::from_millis(cli.retry_delay_ms),
+            timeout: cli
+                .request_timeout_seconds
+                .map(std::time::Duration::from_secs),
+            connect_timeout: cli
+                .connection_timeout_seconds
+                .map(std::time::Duration::from_secs),
         },
     };
     let client = CopilotClient::new(mode);
+    run_with_client(cli, &client)
+}
+
+/// Runs a parsed command against any [`CopilotClientTrait`] implementation (e.g. an in-memory
+/// mock in tests). Connection flags like `--base-url`, `--token`, and `--fixtures-dir` only
+/// matter to [`run`], which builds the real client from them.
+pub fn run_with_client(mut cli: Cli, client: &dyn CopilotClientTrait) -> anyhow::Result<()> {
+    cli.out = match cli.append_output_file.as_deref() {
+        Some(path) => OutputSink::append(path)?,
+        None => OutputSink::open(cli.output_file.as_deref())?,
+    };
+
+    if let Command::Version = &cli.command {
+        return print_version(&cli);
+    }
+
+    if (cli.global_date_from.is_some() || cli.global_date_to.is_some())
+        && !supports_global_dates(&cli.command)
+    {
+        eprintln!("warning: --global-date-from/--global-date-to are ignored by this command");
+    }
+
+    let result = dispatch(&cli, client);
+    cli.out.lock().flush()?;
+    result
+}
+
+/// Renders an error as `{"error": ..., "kind": ...}` for `--format-errors json`.
+pub fn error_json(err: &anyhow::Error) -> String {
+    serde_json::json!({
+        "error": format!("{err:#}"),
+        "kind": error_kind(err),
+    })
+    .to_string()
+}
+
+fn api_error(err: &anyhow::Error) -> Option<&ApiError> {
+    err.chain().find_map(|e| e.downcast_ref::<ApiError>())
+}
+
+/// Process exit code for a failed command: 3 when the API rejected the token, 1 otherwise.
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    match api_error(err) {
+        Some(ApiError::Unauthenticated | ApiError::TokenRefresh(_)) => 3,
+        _ => 1,
+    }
+}
+
+/// Classifies API failures by their `ApiError`; anything else is a best-effort guess from
+/// the message.
+fn error_kind(err: &anyhow::Error) -> &'static str {
+    match api_error(err) {
+        Some(ApiError::Unauthenticated | ApiError::TokenRefresh(_)) => return "auth",
+        Some(ApiError::FixtureMissing(_)) => return "not-found",
+        Some(_) => return "general",
+        None => {}
+    }
+    let msg = format!("{err:#}").to_lowercase();
+    if msg.contains("unauthenticated") || msg.contains("token") {
+        "auth"
+    } else if msg.contains("not found") || msg.contains("could not resolve") {
+        "not-found"
+    } else if msg.contains("invalid")
+        || msg.contains("missing")
+        || msg.contains("must ")
+        || msg.contains("without --yes")
+        || msg.contains("pass --yes")
+    {
+        "validation"
+    } else {
+        "general"
+    }
+}
 
+fn dispatch(cli: &Cli, client: &dyn CopilotClientTrait) -> anyhow::Result<()> {
     match &cli.command {
-        Command::Auth { cmd } => auth::run_auth(&cli, &client, cmd.clone()),
-        Command::Transactions { cmd } => run_transactions(&cli, &client, cmd.clone()),
-        Command::Categories { cmd } => categories::run_categories(&cli, &client, cmd.clone()),
-        Command::Recurrings { cmd } => recurrings::run_recurrings(&cli, &client, cmd.clone()),
-        Command::Tags { cmd } => tags::run_tags(&cli, &client, cmd.clone()),
-        Command::Budgets { cmd } => budgets::run_budgets(&cli, &client, cmd.clone()),
-        Command::Version => unreachable!(),
+        Command::Auth { cmd } => auth::run_auth(cli, client, cmd.clone()),
+        Command::Transactions { cmd } => run_transactions(cli, client, (**cmd).clone()),
+        Command::Categories { cmd } => categories::run_categories(cli, client, cmd.clone()),
+        Command::Recurrings { cmd } => recurrings::run_recurrings(cli, client, cmd.clone()),
+        Command::Tags { cmd } => tags::run_tags(cli, client, cmd.clone()),
+        Command::Accounts { cmd } => accounts::run_accounts(cli, client, cmd.clone()),
+        Command::Budgets { cmd } => budgets::run_budgets(cli, client, cmd.clone()),
+        Command::Watch(args) => watch::run_watch(cli, client, args.clone()),
+        Command::Batch(args) => batch::run_batch(cli, client, args.clone()),
+        Command::Env => env::run_env(cli),
+        Command::Config { cmd } => config::run_config(cli, cmd.clone()),
+        Command::Completions { shell } => {
+            // Flags and `ValueEnum` values (e.g. `--fields`, `--frequency`) come from the clap model.
+            clap_complete::generate(
+                *shell,
+                &mut <Cli as clap::CommandFactory>::command(),
+                "copilot",
+                &mut *cli.out.lock(),
+            );
+            Ok(())
+        }
+        Command::Version => print_version(cli),
+    }
+}
+
+/// `copilot-money-cli <version> (<git hash>, <build date>)`; both come from `build.rs` and are
+/// `unknown` when git or the clock weren't available at build time.
+fn print_version(cli: &Cli) -> anyhow::Result<()> {
+    let (version, git_hash, build_date) = (
+        env!("CARGO_PKG_VERSION"),
+        env!("GIT_HASH"),
+        env!("BUILD_DATE"),
+    );
+    let mut out = cli.out.lock();
+    match cli.output {
+        OutputFormat::Json => writeln!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "version": version,
+                "git_hash": git_hash,
+                "build_date": build_date,
+            }))?
+        )?,
+        OutputFormat::Table => writeln!(
+            out,
+            "copilot-money-cli {version} ({git_hash}, {build_date})"
+        )?,
+    }
+    Ok(())
+}
+
+fn supports_global_dates(cmd: &Command) -> bool {
+    match cmd {
+        Command::Transactions { cmd } => {
+            matches!(
+                **cmd,
+                TransactionsCmd::List(_)
+                    | TransactionsCmd::Search(_)
+                    | TransactionsCmd::BulkReview(_)
+            ) || matches!(**cmd, TransactionsCmd::SetCategory(ref a) if a.all_matching)
+        }
+        Command::Budgets {
+            cmd: BudgetsCmd::Month,
+        }
+        | Command::Batch(_) => true,
+        _ => false,
     }
 }
 
+/// Resolves a `(from, to)` date range, preferring command-level flags over the global ones.
+/// Both ends are normalized to `YYYY-MM-DD`.
+fn resolve_date_range(
+    cli: &Cli,
+    date_from: Option<&str>,
+    date_to: Option<&str>,
+) -> anyhow::Result<(Option<String>, Option<String>)> {
+    let normalize = |flag: &str, v: Option<&str>| -> anyhow::Result<Option<String>> {
+        v.map(|d| {
+            normalize_date(d, cli.date_order)
+                .ok_or_else(|| anyhow::anyhow!("invalid {flag}: {d:?}"))
+        })
+        .transpose()
+    };
+    let from = match date_from {
+        Some(d) => normalize("--date-from", Some(d))?,
+        None => normalize("--global-date-from", cli.global_date_from.as_deref())?,
+    };
+    let to = match date_to {
+        Some(d) => normalize("--date-to", Some(d))?,
+        None => normalize("--global-date-to", cli.global_date_to.as_deref())?,
+    };
+    Ok((from, to))
+}
+
 impl TableRow for KeyValueRow {
     const HEADERS: &'static [&'static str] = &["key", "value"];
 
-    fn cells(&self) -> Vec<Cell> {
+    fn cells(&self, _cli: &Cli) -> Vec<Cell> {
         vec![Cell::new(&self.key), Cell::new(&self.value)]
     }
 }
@@ -621,9 +1611,18 @@ fn value_to_string(v: Option<serde_json::Value>) -> String {
     }
 }
 
+fn value_to_f64(v: Option<&serde_json::Value>) -> Option<f64> {
+    match v? {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
 fn value_to_money_string(v: Option<serde_json::Value>) -> String {
     let s = value_to_string(v);
-    if s.trim().is_empty() {
+    // Some callers stringify JSON with `Value::to_string`, which turns `null` into `"null"`.
+    if s.trim().is_empty() || s.trim().eq_ignore_ascii_case("null") {
         return String::new();
     }
 
@@ -649,7 +1648,11 @@ fn value_to_money_string(v: Option<serde_json::Value>) -> String {
     }
 }
 
-fn normalize_date(s: &str) -> Option<String> {
+/// Normalizes `YYYY-MM-DD` or `NN-NN-YYYY` to `YYYY-MM-DD`.
+///
+/// A year-last date like `04-05-2025` is ambiguous (April 5 or May 4); `order` decides, and
+/// there is no guessing from the values, so `13-01-2025` is rejected under [`DateOrder::Mdy`].
+fn normalize_date(s: &str, order: DateOrder) -> Option<String> {
     let s = s.trim();
     if s.len() != 10 {
         return None;
@@ -663,7 +1666,10 @@ fn normalize_date(s: &str) -> Option<String> {
     let (year, month, day) = if parts[0].len() == 4 {
         (parts[0], parts[1], parts[2])
     } else if parts[2].len() == 4 {
-        (parts[2], parts[0], parts[1])
+        match order {
+            DateOrder::Mdy => (parts[2], parts[0], parts[1]),
+            DateOrder::Dmy => (parts[2], parts[1], parts[0]),
+        }
     } else {
         return None;
     };
@@ -677,14 +1683,16 @@ fn normalize_date(s: &str) -> Option<String> {
     Some(format!("{y:04}-{m:02}-{d:02}"))
 }
 
+/// Server-side `TransactionFilter`. Client-side filtering still runs afterwards, so fixtures
+/// (which ignore variables) and partial server support behave the same.
 fn build_transactions_filter(reviewed: bool, unreviewed: bool) -> Option<serde_json::Value> {
+    let mut filter = serde_json::Map::new();
     if reviewed {
-        Some(serde_json::json!({ "isReviewed": true }))
+        filter.insert("isReviewed".to_string(), true.into());
     } else if unreviewed {
-        Some(serde_json::json!({ "isReviewed": false }))
-    } else {
-        None
+        filter.insert("isReviewed".to_string(), false.into());
     }
+    (!filter.is_empty()).then_some(serde_json::Value::Object(filter))
 }
 
 fn flatten_categories_for_lookup(categories: &[Category]) -> Vec<(CategoryId, String)> {
@@ -702,7 +1710,9 @@ fn flatten_categories_for_lookup(categories: &[Category]) -> Vec<(CategoryId, St
     out
 }
 
-fn category_name_map(client: &CopilotClient) -> anyhow::Result<HashMap<CategoryId, String>> {
+fn category_name_map(
+    client: &dyn CopilotClientTrait,
+) -> anyhow::Result<HashMap<CategoryId, String>> {
     let categories = client.list_categories(false, false, false)?;
     let mut out = HashMap::new();
     for (id, name) in flatten_categories_for_lookup(&categories) {
@@ -712,7 +1722,7 @@ fn category_name_map(client: &CopilotClient) -> anyhow::Result<HashMap<CategoryI
 }
 
 fn resolve_category_id(
-    client: &CopilotClient,
+    client: &dyn CopilotClientTrait,
     category_id: Option<&CategoryId>,
     category_name: Option<&str>,
 ) -> anyhow::Result<Option<CategoryId>> {
@@ -722,21 +1732,39 @@ fn resolve_category_id(
     let Some(name) = category_name else {
         return Ok(None);
     };
+    let categories = client.list_categories(false, false, false)?;
+    match_category_name(&categories, name).map(Some)
+}
 
+/// Resolves several category names with a single categories request.
+fn resolve_category_names(
+    client: &dyn CopilotClientTrait,
+    names: &[String],
+) -> anyhow::Result<Vec<CategoryId>> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let categories = client.list_categories(false, false, false)?;
+    names
+        .iter()
+        .map(|name| match_category_name(&categories, name))
+        .collect()
+}
+
+fn match_category_name(categories: &[Category], name: &str) -> anyhow::Result<CategoryId> {
     let want = name.trim().to_lowercase();
     if want.is_empty() {
         anyhow::bail!("empty --category");
     }
 
-    let categories = client.list_categories(false, false, false)?;
-    let matches = flatten_categories_for_lookup(&categories)
+    let matches = flatten_categories_for_lookup(categories)
         .into_iter()
         .filter(|(_, n)| n.to_lowercase() == want)
         .collect::<Vec<_>>();
 
     match matches.as_slice() {
         [] => anyhow::bail!("no category named {:?}", name),
-        [(id, _)] => Ok(Some(id.clone())),
+        [(id, _)] => Ok(id.clone()),
         many => anyhow::bail!(
             "category name {:?} is ambiguous ({} matches); use --category-id instead",
             name,
@@ -749,7 +1777,13 @@ fn should_color(cli: &Cli) -> bool {
     match cli.color {
         ColorMode::Always => true,
         ColorMode::Never => false,
-        ColorMode::Auto => std::io::stdout().is_terminal(),
+        ColorMode::Auto => {
+            cli.output_file
+                .as_ref()
+                .is_none_or(|p| p.as_os_str() == "-")
+                && cli.append_output_file.is_none()
+                && std::io::stdout().is_terminal()
+        }
     }
 }
 
@@ -772,31 +1806,14 @@ fn confirm_write(cli: &Cli, action: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) -> anyhow::Result<()> {
+fn run_transactions(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    cmd: TransactionsCmd,
+) -> anyhow::Result<()> {
     match cmd {
         TransactionsCmd::List(args) => {
-            let category_id =
-                resolve_category_id(client, args.category_id.as_ref(), args.category.as_deref())?;
-            let filter = build_transactions_filter(args.reviewed, args.unreviewed);
-            let sort = sort_to_graphql(args.sort);
-            let (items, page_info) = fetch_transactions_with_filter_sort(
-                client,
-                args.limit,
-                args.after.clone(),
-                args.pages,
-                args.all,
-                filter,
-                sort,
-            )?;
-            let filtered = filter_transactions(
-                items,
-                args.reviewed,
-                args.unreviewed,
-                category_id.as_ref(),
-                &args.tag,
-                args.name_contains.as_deref(),
-                args.date.as_deref(),
-            );
+            let (filtered, page_info) = fetch_listed_transactions(cli, client, &args)?;
             render_transactions_output(
                 cli,
                 client,
@@ -804,11 +1821,16 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
                 page_info,
                 args.page_info,
                 &args.fields,
+                &mut *cli.out.lock(),
             )
         }
         TransactionsCmd::Search(args) => {
+            let (date_from, date_to) =
+                resolve_date_range(cli, args.date_from.as_deref(), args.date_to.as_deref())?;
             let category_id =
                 resolve_category_id(client, args.category_id.as_ref(), args.category.as_deref())?;
+            // The API has no full-text `query` argument (nor a captured name filter), so the
+            // query is matched client-side.
             let filter = build_transactions_filter(args.reviewed, args.unreviewed);
             let sort = sort_to_graphql(args.sort);
             let (items, page_info) = fetch_transactions_with_filter_sort(
@@ -822,12 +1844,19 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
             )?;
             let filtered = filter_transactions(
                 items,
-                args.reviewed,
-                args.unreviewed,
-                category_id.as_ref(),
-                &args.tag,
-                Some(&args.query),
-                args.date.as_deref(),
+                &TransactionFilters {
+                    reviewed: args.reviewed,
+                    unreviewed: args.unreviewed,
+                    category_id: category_id.as_ref(),
+                    tags: &args.tag,
+                    query: Some(&args.query),
+                    case_sensitive: args.case_sensitive,
+                    date: args.date.as_deref(),
+                    date_order: cli.date_order,
+                    date_from: date_from.as_deref(),
+                    date_to: date_to.as_deref(),
+                    ..Default::default()
+                },
             );
             render_transactions_output(
                 cli,
@@ -836,51 +1865,24 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
                 page_info,
                 args.page_info,
                 &args.fields,
+                &mut *cli.out.lock(),
             )
         }
         TransactionsCmd::Show(args) => {
             let items = client.list_transactions(args.limit)?;
             let found = items.into_iter().find(|t| t.id == args.id);
             match found {
-                Some(t) => render_output(
-                    cli,
-                    vec![
-                        KeyValueRow {
-                            key: "id".to_string(),
-                            value: t.id.to_string(),
-                        },
-                        KeyValueRow {
-                            key: "date".to_string(),
-                            value: t.date.unwrap_or_default(),
-                        },
-                        KeyValueRow {
-                            key: "name".to_string(),
-                            value: t.name.unwrap_or_default(),
-                        },
-                        KeyValueRow {
-                            key: "amount".to_string(),
-                            value: value_to_money_string(t.amount),
-                        },
-                        KeyValueRow {
-                            key: "category_id".to_string(),
-                            value: t
-                                .category_id
-                                .as_ref()
-                                .map(|c| c.to_string())
-                                .unwrap_or_default(),
-                        },
-                        KeyValueRow {
-                            key: "reviewed".to_string(),
-                            value: t.is_reviewed.unwrap_or(false).to_string(),
-                        },
-                    ],
-                ),
+                Some(t) => render_output(cli, transaction_detail_rows(t)),
                 None => anyhow::bail!("transaction not found"),
             }
         }
         TransactionsCmd::Review(args) => {
             if cli.dry_run {
-                println!("dry-run: would mark reviewed: {:?}", args.ids);
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would mark reviewed: {:?}",
+                    args.ids
+                )?;
                 return Ok(());
             }
             confirm_write(cli, &format!("Mark reviewed: {:?}", args.ids))?;
@@ -891,34 +1893,90 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
         }
         TransactionsCmd::Unreview(args) => {
             if cli.dry_run {
-                println!("dry-run: would mark unreviewed: {:?}", args.ids);
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would mark unreviewed: {:?}",
+                    args.ids
+                )?;
+                return Ok(());
+            }
+            confirm_write(cli, &format!("Mark unreviewed: {:?}", args.ids))?;
+            let txns = resolve_transactions_by_ids(client, &args.ids)?;
+            let refs = build_transaction_id_refs(&txns)?;
+            let result = client.bulk_edit_transactions_reviewed(refs, false)?;
+            render_bulk_edit_result(cli, result)
+        }
+        TransactionsCmd::BulkReview(args) => {
+            let txns = fetch_bulk_review_transactions(cli, client, &args)?;
+            if txns.is_empty() {
+                writeln!(cli.out.lock(), "no matching transactions")?;
+                return Ok(());
+            }
+            let n = txns.len();
+            if cli.dry_run {
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would mark {n} transaction(s) reviewed: {:?}",
+                    txns.iter().map(|t| t.id.as_str()).collect::<Vec<_>>()
+                )?;
                 return Ok(());
             }
-            confirm_write(cli, &format!("Mark unreviewed: {:?}", args.ids))?;
-            let txns = resolve_transactions_by_ids(client, &args.ids)?;
+            confirm_write(cli, &format!("Mark {n} matching transaction(s) reviewed"))?;
             let refs = build_transaction_id_refs(&txns)?;
-            let result = client.bulk_edit_transactions_reviewed(refs, false)?;
+            let result = client.bulk_edit_transactions_reviewed(refs, true)?;
             render_bulk_edit_result(cli, result)
         }
         TransactionsCmd::SetCategory(args) => {
+            if args.all_matching && !args.ids.is_empty() {
+                anyhow::bail!("pass either transaction ids or --all-matching, not both");
+            }
+            if args.all_matching && !cli.yes && !cli.dry_run {
+                anyhow::bail!(
+                    "--all-matching edits every matching transaction; pass --yes (or --dry-run)"
+                );
+            }
+            let matching = if args.all_matching {
+                Some(fetch_matching_transactions(cli, client, &args.filters)?)
+            } else {
+                None
+            };
+            let ids = match &matching {
+                Some(txns) => txns.iter().map(|t| t.id.clone()).collect(),
+                None => args.ids.clone(),
+            };
             if cli.dry_run {
-                println!(
+                writeln!(
+                    cli.out.lock(),
                     "dry-run: would set category {:?}/{:?} for {:?}",
-                    args.category_id, args.category, args.ids
-                );
+                    args.category_id,
+                    args.category,
+                    ids
+                )?;
+                return Ok(());
+            }
+            if ids.is_empty() {
+                writeln!(cli.out.lock(), "no matching transactions")?;
                 return Ok(());
             }
             let category_id =
                 resolve_category_id(client, args.category_id.as_ref(), args.category.as_deref())?
                     .ok_or_else(|| anyhow::anyhow!("missing category target"))?;
+            let target = if args.all_matching {
+                format!("{} matching transaction(s)", ids.len())
+            } else {
+                format!("{ids:?}")
+            };
             confirm_write(
                 cli,
                 &format!(
-                    "Set category {:?}/{:?} for {:?}",
-                    category_id, args.category, args.ids
+                    "Set category {:?}/{:?} for {target}",
+                    category_id, args.category
                 ),
             )?;
-            let txns = resolve_transactions_by_ids(client, &args.ids)?;
+            let txns = match matching {
+                Some(txns) => txns,
+                None => resolve_transactions_by_ids(client, &ids)?,
+            };
             let mut updated = Vec::new();
             for txn in txns {
                 let (item_id, account_id) = require_item_and_account(&txn)?;
@@ -934,10 +1992,12 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
         }
         TransactionsCmd::AssignRecurring(args) => {
             if cli.dry_run {
-                println!(
+                writeln!(
+                    cli.out.lock(),
                     "dry-run: would assign recurring {} for {:?}",
-                    args.recurring_id, args.ids
-                );
+                    args.recurring_id,
+                    args.ids
+                )?;
                 return Ok(());
             }
             confirm_write(
@@ -960,15 +2020,17 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
         }
         TransactionsCmd::SetNotes(args) => {
             if cli.dry_run {
-                println!(
-                    "dry-run: would set notes for {:?} (clear={})",
-                    args.ids, args.clear
-                );
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would set notes for {:?} (mode={})",
+                    args.ids,
+                    args.mode_label()
+                )?;
                 return Ok(());
             }
             confirm_write(
                 cli,
-                &format!("Set notes for {:?} (clear={})", args.ids, args.clear),
+                &format!("Set notes for {:?} (mode={})", args.ids, args.mode_label()),
             )?;
             if !args.clear && args.notes.is_none() {
                 anyhow::bail!("use --notes <TEXT> or --clear");
@@ -977,10 +2039,15 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
             let mut updated = Vec::new();
             for txn in txns {
                 let (item_id, account_id) = require_item_and_account(&txn)?;
+                let text = args.notes.as_deref().unwrap_or_default();
                 let input = if args.clear {
                     serde_json::json!({ "userNotes": "" })
+                } else if args.append {
+                    serde_json::json!({ "userNotes": append_notes(txn.user_notes.as_deref(), text) })
+                } else if args.prepend {
+                    serde_json::json!({ "userNotes": prepend_notes(txn.user_notes.as_deref(), text) })
                 } else {
-                    serde_json::json!({ "userNotes": args.notes.clone().unwrap_or_default() })
+                    serde_json::json!({ "userNotes": text })
                 };
                 let t = client.edit_transaction(&item_id, &account_id, &txn.id, input)?;
                 updated.push(t);
@@ -989,24 +2056,36 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
         }
         TransactionsCmd::SetTags(args) => {
             if cli.dry_run {
-                println!(
-                    "dry-run: would update tags mode={:?} tag_ids={:?} for {:?}",
-                    args.mode, args.tag_ids, args.ids
-                );
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would update tags mode={:?} tag_ids={:?} add_names={:?} remove_names={:?} for {:?}",
+                    args.mode,
+                    args.tag_ids,
+                    args.add_names,
+                    args.remove_names,
+                    args.ids
+                )?;
                 return Ok(());
             }
             confirm_write(
                 cli,
                 &format!(
-                    "Update tags mode={:?} tag_ids={:?} for {:?}",
-                    args.mode, args.tag_ids, args.ids
+                    "Update tags mode={:?} tag_ids={:?} add_names={:?} remove_names={:?} for {:?}",
+                    args.mode, args.tag_ids, args.add_names, args.remove_names, args.ids
                 ),
             )?;
+            let by_name = !args.add_names.is_empty() || !args.remove_names.is_empty();
             if (args.mode == TagUpdateMode::Add || args.mode == TagUpdateMode::Remove)
                 && args.tag_ids.is_empty()
+                && !by_name
             {
                 anyhow::bail!("--tag-id is required for --mode add/remove");
             }
+            let add_ids = tags::resolve_tag_ids_by_names(client, &args.add_names)?;
+            let remove_ids = tags::resolve_tag_ids_by_names(client, &args.remove_names)?;
+            // With only names given, edit the existing tags rather than applying `--mode set`
+            // with an empty list (which would clear them first).
+            let names_only = by_name && args.tag_ids.is_empty();
 
             let txns = resolve_transactions_by_ids(client, &args.ids)?;
             let mut updated = Vec::new();
@@ -1019,7 +2098,8 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
                     .map(|ts| ts.iter().map(|t| t.id.clone()).collect::<HashSet<_>>())
                     .unwrap_or_default();
 
-                let next_ids: Vec<TagId> = match args.mode {
+                let mut next_ids: Vec<TagId> = match args.mode {
+                    _ if names_only => existing.into_iter().collect(),
                     TagUpdateMode::Set => args.tag_ids.clone(),
                     TagUpdateMode::Add => {
                         let mut out = existing;
@@ -1036,6 +2116,12 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
                         out.into_iter().collect()
                     }
                 };
+                for id in &add_ids {
+                    if !next_ids.contains(id) {
+                        next_ids.push(id.clone());
+                    }
+                }
+                next_ids.retain(|id| !remove_ids.contains(id));
 
                 let t = client.edit_transaction(
                     &item_id,
@@ -1055,12 +2141,13 @@ fn run_transactions(cli: &Cli, client: &CopilotClient, cmd: TransactionsCmd) ->
         }
         TransactionsCmd::Edit(args) => {
             if cli.dry_run {
-                println!(
+                writeln!(
+                    cli.out.lock(),
                     "dry-run: would edit transactions {:?} (type={:?}, input_json={})",
                     args.ids,
                     args.type_,
                     args.input_json.is_some()
-                );
+                )?;
                 return Ok(());
             }
             confirm_write(cli, &format!("Edit transactions {:?}", args.ids))?;
@@ -1122,9 +2209,37 @@ fn build_transaction_id_refs(txns: &[Transaction]) -> anyhow::Result<Vec<Transac
 }
 
 fn resolve_transactions_by_ids(
-    client: &CopilotClient,
+    client: &dyn CopilotClientTrait,
     ids: &[TransactionId],
 ) -> anyhow::Result<Vec<Transaction>> {
+    let (mut found, scanned) = scan_for_transactions(client, ids)?;
+
+    let mut missing = Vec::new();
+    let mut ordered = Vec::new();
+    for id in ids {
+        match found.remove(id) {
+            Some(t) => ordered.push(t),
+            None => missing.push(id.to_string()),
+        }
+    }
+
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "could not resolve {} transaction ids after scanning {scanned} transactions: {:?}",
+            missing.len(),
+            missing
+        );
+    }
+
+    Ok(ordered)
+}
+
+/// Pages through recent transactions until every id is found (or the scan limit is hit).
+/// Returns what was found, plus how many transactions were scanned.
+fn scan_for_transactions(
+    client: &dyn CopilotClientTrait,
+    ids: &[TransactionId],
+) -> anyhow::Result<(HashMap<TransactionId, Transaction>, usize)> {
     let want: HashSet<TransactionId> = ids.iter().cloned().collect();
     let mut found: HashMap<TransactionId, Transaction> = HashMap::new();
 
@@ -1154,24 +2269,7 @@ fn resolve_transactions_by_ids(
         break;
     }
 
-    let mut missing = Vec::new();
-    let mut ordered = Vec::new();
-    for id in ids {
-        match found.remove(id) {
-            Some(t) => ordered.push(t),
-            None => missing.push(id.to_string()),
-        }
-    }
-
-    if !missing.is_empty() {
-        anyhow::bail!(
-            "could not resolve {} transaction ids after scanning {scanned} transactions: {:?}",
-            missing.len(),
-            missing
-        );
-    }
-
-    Ok(ordered)
+    Ok((found, scanned))
 }
 
 #[derive(Debug, Serialize)]
@@ -1182,23 +2280,102 @@ struct BulkEditJsonOutput {
 
 fn render_bulk_edit_result(cli: &Cli, result: BulkEditTransactionsResult) -> anyhow::Result<()> {
     if !result.failed.is_empty() {
+        let failed = result.failed.len();
         if cli.output == OutputFormat::Json {
             let out = BulkEditJsonOutput {
                 updated: result.updated,
                 failed: result.failed,
             };
             let s = serde_json::to_string_pretty(&out)?;
-            println!("{s}");
+            writeln!(cli.out.lock(), "{s}")?;
             return Ok(());
         }
-        anyhow::bail!(
-            "bulk edit failed for {} transaction(s)",
-            result.failed.len()
-        );
+        // Show the partial success before failing, so updated rows aren't hidden.
+        let mut rows: Vec<BulkEditRow> = result
+            .updated
+            .iter()
+            .map(|t| BulkEditRow::new("ok", Some(t), None, None))
+            .collect();
+        rows.extend(result.failed.iter().map(|f| {
+            BulkEditRow::new(
+                "failed",
+                f.transaction.as_ref(),
+                f.error_code.as_deref(),
+                f.error.as_deref(),
+            )
+        }));
+        render_output(cli, rows)?;
+        anyhow::bail!("bulk edit failed for {failed} transaction(s)");
     }
     render_transactions_updated(cli, result.updated)
 }
 
+#[derive(Debug, Serialize)]
+struct BulkEditRow {
+    status: &'static str,
+    id: String,
+    date: String,
+    name: String,
+    error_code: String,
+    error: String,
+}
+
+impl BulkEditRow {
+    fn new(
+        status: &'static str,
+        t: Option<&Transaction>,
+        error_code: Option<&str>,
+        error: Option<&str>,
+    ) -> Self {
+        Self {
+            status,
+            id: t.map(|t| t.id.to_string()).unwrap_or_default(),
+            date: t.and_then(|t| t.date.clone()).unwrap_or_default(),
+            name: t.and_then(|t| t.name.clone()).unwrap_or_default(),
+            error_code: error_code.unwrap_or_default().to_string(),
+            error: error.unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl TableRow for BulkEditRow {
+    const HEADERS: &'static [&'static str] =
+        &["status", "id", "date", "name", "error_code", "error"];
+
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
+        let mut status = Cell::new(self.status);
+        if should_color(cli) {
+            status = status.fg(if self.status == "ok" {
+                Color::Green
+            } else {
+                Color::Red
+            });
+        }
+        vec![
+            status,
+            Cell::new(shorten_id_for_table(cli, &self.id)),
+            Cell::new(&self.date),
+            Cell::new(&self.name),
+            Cell::new(&self.error_code),
+            Cell::new(&self.error),
+        ]
+    }
+}
+
+fn append_notes(existing: Option<&str>, text: &str) -> String {
+    match existing.map(str::trim_end).filter(|s| !s.is_empty()) {
+        Some(e) => format!("{e} {text}"),
+        None => text.to_string(),
+    }
+}
+
+fn prepend_notes(existing: Option<&str>, text: &str) -> String {
+    match existing.map(str::trim_start).filter(|s| !s.is_empty()) {
+        Some(e) => format!("{text} {e}"),
+        None => text.to_string(),
+    }
+}
+
 fn render_transactions_updated(cli: &Cli, items: Vec<Transaction>) -> anyhow::Result<()> {
     const DEFAULT_FIELDS: &[TransactionField] = &[
         TransactionField::Date,
@@ -1217,10 +2394,12 @@ fn render_transactions_updated(cli: &Cli, items: Vec<Transaction>) -> anyhow::Re
                 page_info: None,
             };
             let s = serde_json::to_string_pretty(&out)?;
-            println!("{s}");
+            writeln!(cli.out.lock(), "{s}")?;
             Ok(())
         }
-        OutputFormat::Table => render_transactions_table(cli, &items, DEFAULT_FIELDS, None),
+        OutputFormat::Table => {
+            render_transactions_table(cli, &items, DEFAULT_FIELDS, None, &mut *cli.out.lock())
+        }
     }
 }
 
@@ -1231,8 +2410,191 @@ struct TransactionsJsonOutput {
     page_info: Option<PageInfo>,
 }
 
+/// Fetches and filters transactions for `transactions list` (and commands that reuse its flags).
+fn fetch_listed_transactions(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    args: &TransactionsListArgs,
+) -> anyhow::Result<(Vec<Transaction>, PageInfo)> {
+    let (date_from, date_to) =
+        resolve_date_range(cli, args.date_from.as_deref(), args.date_to.as_deref())?;
+    let category_id =
+        resolve_category_id(client, args.category_id.as_ref(), args.category.as_deref())?;
+    let mut exclude_category_ids = args.exclude_category_id.clone();
+    exclude_category_ids.extend(resolve_category_names(client, &args.exclude_category)?);
+    if let (Some(min), Some(max)) = (args.amount_min, args.amount_max)
+        && min > max
+    {
+        anyhow::bail!("--amount-min ({min}) is greater than --amount-max ({max})");
+    }
+    let mut exclude_types = args.exclude_type.clone();
+    if args.hide_internal_transfers {
+        exclude_types.push(TransactionType::InternalTransfer);
+    }
+    // `--name-contains` is matched client-side only: no captured `TransactionFilter` field
+    // for it, and sending an unknown input field could make the server reject the query.
+    let filter = build_transactions_filter(args.reviewed, args.unreviewed);
+    let sort = sort_to_graphql(args.sort);
+    let (page_size, all) = if args.page_size == 0 {
+        eprintln!("note: --page-size/--limit 0 is treated as --all; use --all for clarity");
+        (200, true)
+    } else {
+        (args.page_size, args.all)
+    };
+    // `--since-cursor` reads from the head of the (newest-first) feed until it reaches
+    // transactions it has already seen; paging `after` an API cursor would walk into older history.
+    let (items, page_info) = if let Some(cursor) = args.since_cursor.as_deref() {
+        let mut marker = watch::WatchMarker::parse_cursor(cursor)?;
+        let items = watch::fetch_newer(client, &marker, page_size)?;
+        marker.advance(&items);
+        // Printed even when nothing is new, so a saved cursor can always be overwritten.
+        eprintln!("since-cursor: {}", marker.to_cursor());
+        (items, PageInfo::default())
+    } else {
+        let mut after = args.after.clone();
+        if let Some(id) = args.after_id.as_ref() {
+            after = Some(find_transaction_cursor(
+                client,
+                page_size,
+                id,
+                filter.clone(),
+                sort.clone(),
+            )?);
+        }
+        if args.skip_pages > 0 {
+            after = match skip_transaction_pages(
+                client,
+                page_size,
+                after,
+                args.skip_pages,
+                filter.clone(),
+                sort.clone(),
+            )? {
+                Some(cursor) => Some(cursor),
+                // Skipped past the last page: nothing left to show.
+                None => {
+                    let page_info = PageInfo {
+                        has_next_page: Some(false),
+                        ..PageInfo::default()
+                    };
+                    return Ok((Vec::new(), page_info));
+                }
+            };
+        }
+        fetch_transactions_with_filter_sort(
+            client, page_size, after, args.pages, all, filter, sort,
+        )?
+    };
+    let mut filtered = filter_transactions(
+        items,
+        &TransactionFilters {
+            reviewed: args.reviewed,
+            unreviewed: args.unreviewed,
+            category_id: category_id.as_ref(),
+            exclude_category_ids: &exclude_category_ids,
+            exclude_types: &exclude_types,
+            tags: &args.tag,
+            tag_ids: &args.tag_ids,
+            query: args.name_contains.as_deref(),
+            case_sensitive: args.case_sensitive,
+            date: args.date.as_deref(),
+            date_order: cli.date_order,
+            date_from: date_from.as_deref(),
+            date_to: date_to.as_deref(),
+            has_recurring: match (args.has_recurring, args.no_recurring) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            amount_positive: match (args.amount_positive, args.amount_negative) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            pending: match (args.pending_only, args.exclude_pending) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            amount_min: args.amount_min,
+            amount_max: args.amount_max,
+        },
+    );
+    if let Some(field) = args.client_sort {
+        if field == TransactionField::All {
+            anyhow::bail!("--client-sort needs a single field, not `all`");
+        }
+        sort_transactions_by_field(&mut filtered, field, args.client_sort_desc);
+    }
+    Ok((filtered, page_info))
+}
+
+/// Fetches every transaction (all pages) matching the `bulk-review` filters.
+fn fetch_bulk_review_transactions(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    args: &TransactionsBulkReviewArgs,
+) -> anyhow::Result<Vec<Transaction>> {
+    let (date_from, date_to) =
+        resolve_date_range(cli, args.date_from.as_deref(), args.date_to.as_deref())?;
+    let category_id =
+        resolve_category_id(client, args.category_id.as_ref(), args.category.as_deref())?;
+    if let (Some(min), Some(max)) = (args.amount_min, args.amount_max)
+        && min > max
+    {
+        anyhow::bail!("--amount-min ({min}) is greater than --amount-max ({max})");
+    }
+    let filter = build_transactions_filter(false, args.unreviewed);
+    let (items, _) = fetch_transactions_with_filter_sort(client, 200, None, 1, true, filter, None)?;
+    Ok(filter_transactions(
+        items,
+        &TransactionFilters {
+            unreviewed: args.unreviewed,
+            category_id: category_id.as_ref(),
+            exclude_types: &args.exclude_type,
+            tags: &args.tag,
+            tag_ids: &args.tag_ids,
+            query: args.name_contains.as_deref(),
+            case_sensitive: args.case_sensitive,
+            date: args.date.as_deref(),
+            date_order: cli.date_order,
+            date_from: date_from.as_deref(),
+            date_to: date_to.as_deref(),
+            amount_min: args.amount_min,
+            amount_max: args.amount_max,
+            ..Default::default()
+        },
+    ))
+}
+
+/// Fetches every transaction (all pages) matching `--all-matching` filters.
+fn fetch_matching_transactions(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    f: &TransactionMatchArgs,
+) -> anyhow::Result<Vec<Transaction>> {
+    let (date_from, date_to) =
+        resolve_date_range(cli, f.date_from.as_deref(), f.date_to.as_deref())?;
+    let filter = build_transactions_filter(f.reviewed, f.unreviewed);
+    let (items, _) = fetch_transactions_with_filter_sort(client, 200, None, 1, true, filter, None)?;
+    Ok(filter_transactions(
+        items,
+        &TransactionFilters {
+            reviewed: f.reviewed,
+            unreviewed: f.unreviewed,
+            tags: &f.tag,
+            query: f.name_contains.as_deref(),
+            date: f.date.as_deref(),
+            date_order: cli.date_order,
+            date_from: date_from.as_deref(),
+            date_to: date_to.as_deref(),
+            ..Default::default()
+        },
+    ))
+}
+
 fn fetch_transactions_with_filter_sort(
-    client: &CopilotClient,
+    client: &dyn CopilotClientTrait,
     page_size: usize,
     after: Option<String>,
     pages: usize,
@@ -1266,90 +2628,303 @@ fn fetch_transactions_with_filter_sort(
         }
     }
 
-    Ok((
-        out,
-        last_page_info.unwrap_or(PageInfo {
-            end_cursor: None,
-            has_next_page: None,
-            has_previous_page: None,
-            start_cursor: None,
-        }),
-    ))
+    Ok((out, last_page_info.unwrap_or_default()))
 }
 
-fn filter_transactions(
-    items: Vec<Transaction>,
+/// Pages through `skip` pages without keeping them; returns the cursor to continue from, or
+/// `None` if the results ran out first.
+fn skip_transaction_pages(
+    client: &dyn CopilotClientTrait,
+    page_size: usize,
+    mut cursor: Option<String>,
+    skip: usize,
+    filter: Option<serde_json::Value>,
+    sort: Option<serde_json::Value>,
+) -> anyhow::Result<Option<String>> {
+    for _ in 0..skip {
+        let page = client.list_transactions_page(
+            page_size,
+            cursor.clone(),
+            filter.clone(),
+            sort.clone(),
+        )?;
+        if !page.page_info.has_next_page.unwrap_or(false) {
+            return Ok(None);
+        }
+        cursor = page.page_info.end_cursor;
+        if cursor.is_none() {
+            return Ok(None);
+        }
+    }
+    Ok(cursor)
+}
+
+/// Pages from the start until transaction `id` shows up and returns its edge cursor, so the
+/// next fetch continues right after it. O(pages before it) requests.
+fn find_transaction_cursor(
+    client: &dyn CopilotClientTrait,
+    page_size: usize,
+    id: &TransactionId,
+    filter: Option<serde_json::Value>,
+    sort: Option<serde_json::Value>,
+) -> anyhow::Result<String> {
+    let mut cursor = None;
+    loop {
+        let page =
+            client.list_transactions_page(page_size, cursor, filter.clone(), sort.clone())?;
+        if let Some(i) = page.transactions.iter().position(|t| &t.id == id) {
+            return page.cursors.into_iter().nth(i).flatten().ok_or_else(|| {
+                anyhow::anyhow!("transaction {id} has no pagination cursor; use --after instead")
+            });
+        }
+        cursor = page.page_info.end_cursor;
+        if !page.page_info.has_next_page.unwrap_or(false) || cursor.is_none() {
+            anyhow::bail!("--after-id: transaction {id} not found");
+        }
+    }
+}
+
+/// Client-side transaction filters shared by `transactions list` and `transactions search`.
+#[derive(Debug, Default)]
+struct TransactionFilters<'a> {
     reviewed: bool,
     unreviewed: bool,
-    category_id: Option<&CategoryId>,
-    tags: &[String],
-    query: Option<&str>,
-    date: Option<&str>,
-) -> Vec<Transaction> {
-    let q = query.map(|s| s.to_lowercase());
-    let want_tags = tags.iter().map(|t| t.to_lowercase()).collect::<Vec<_>>();
+    category_id: Option<&'a CategoryId>,
+    /// Transactions in any of these categories are dropped.
+    exclude_category_ids: &'a [CategoryId],
+    /// Transactions of any of these types are dropped.
+    exclude_types: &'a [TransactionType],
+    tags: &'a [String],
+    tag_ids: &'a [TagId],
+    query: Option<&'a str>,
+    /// Match `query` exactly as typed instead of lowercasing both sides.
+    case_sensitive: bool,
+    date: Option<&'a str>,
+    /// How to read `date` when it is written year-last.
+    date_order: DateOrder,
+    /// Inclusive lower bound, normalized to `YYYY-MM-DD`.
+    date_from: Option<&'a str>,
+    /// Inclusive upper bound, normalized to `YYYY-MM-DD`.
+    date_to: Option<&'a str>,
+    /// `Some(true)`: only transactions with a recurring; `Some(false)`: only without.
+    has_recurring: Option<bool>,
+    /// `Some(true)`: only amounts > 0; `Some(false)`: only amounts < 0.
+    amount_positive: Option<bool>,
+    /// `Some(true)`: only pending; `Some(false)`: only posted.
+    pending: Option<bool>,
+    /// Inclusive signed bounds; transactions without an amount are dropped when either is set.
+    amount_min: Option<f64>,
+    amount_max: Option<f64>,
+}
+
+fn filter_transactions(items: Vec<Transaction>, f: &TransactionFilters<'_>) -> Vec<Transaction> {
+    let fold = |s: &str| {
+        if f.case_sensitive {
+            s.to_string()
+        } else {
+            s.to_lowercase()
+        }
+    };
+    let q = f.query.map(fold);
+    let want_tags = f.tags.iter().map(|t| t.to_lowercase()).collect::<Vec<_>>();
 
     items
         .into_iter()
         .filter(|t| {
-            if reviewed && !t.is_reviewed.unwrap_or(false) {
+            if f.reviewed && !t.is_reviewed.unwrap_or(false) {
                 return false;
             }
-            if unreviewed && t.is_reviewed.unwrap_or(false) {
+            if f.unreviewed && t.is_reviewed.unwrap_or(false) {
                 return false;
             }
-            if let Some(cat) = category_id
+            if let Some(cat) = f.category_id
                 && t.category_id.as_ref() != Some(cat)
             {
                 return false;
             }
+            if let Some(cat) = t.category_id.as_ref()
+                && f.exclude_category_ids.contains(cat)
+            {
+                return false;
+            }
+            if let Some(ty) = t.txn_type
+                && f.exclude_types.contains(&ty)
+            {
+                return false;
+            }
+            if let Some(want) = f.has_recurring
+                && t.recurring_id.is_some() != want
+            {
+                return false;
+            }
+            if let Some(want) = f.pending
+                && t.is_pending.unwrap_or(false) != want
+            {
+                return false;
+            }
+            if let Some(positive) = f.amount_positive {
+                let amount = value_to_f64(t.amount.as_ref()).unwrap_or(0.0);
+                if (positive && amount <= 0.0) || (!positive && amount >= 0.0) {
+                    return false;
+                }
+            }
+            if f.amount_min.is_some() || f.amount_max.is_some() {
+                let Some(amount) = value_to_f64(t.amount.as_ref()) else {
+                    return false;
+                };
+                if f.amount_min.is_some_and(|min| amount < min)
+                    || f.amount_max.is_some_and(|max| amount > max)
+                {
+                    return false;
+                }
+            }
             if let Some(q) = &q {
-                let name = t.name.as_deref().unwrap_or("").to_lowercase();
+                let name = fold(t.name.as_deref().unwrap_or(""));
                 if !name.contains(q) {
                     return false;
                 }
             }
-            if let Some(d) = date {
-                let want = normalize_date(d).unwrap_or_else(|| d.to_string());
+            if let Some(d) = f.date {
+                let want = normalize_date(d, f.date_order).unwrap_or_else(|| d.to_string());
                 if t.date.as_deref().unwrap_or("") != want {
                     return false;
                 }
             }
-            if want_tags.is_empty() {
+            if let Some(from) = f.date_from
+                && t.date.as_deref().unwrap_or("") < from
+            {
+                return false;
+            }
+            if let Some(to) = f.date_to
+                && t.date.as_deref().unwrap_or("") > to
+            {
+                return false;
+            }
+            if want_tags.is_empty() && f.tag_ids.is_empty() {
                 return true;
             }
-            let txn_tags = t
-                .tags
-                .as_ref()
-                .map(|ts| {
-                    ts.iter()
-                        .filter_map(|tag| tag.name.as_ref())
-                        .map(|s| s.to_lowercase())
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or_default();
-            txn_tags.iter().any(|t| want_tags.iter().any(|w| w == t))
+            let txn_tags = t.tags.as_deref().unwrap_or_default();
+            let name_match = txn_tags
+                .iter()
+                .filter_map(|tag| tag.name.as_ref())
+                .map(|s| s.to_lowercase())
+                .any(|name| want_tags.contains(&name));
+            let id_match = txn_tags.iter().any(|tag| f.tag_ids.contains(&tag.id));
+            name_match || id_match
         })
         .collect()
 }
 
+/// Key/value rows for `transactions show`; missing values are shown as empty strings.
+fn transaction_detail_rows(t: Transaction) -> Vec<KeyValueRow> {
+    fn id_string<T: std::fmt::Display>(id: Option<T>) -> String {
+        id.map(|i| i.to_string()).unwrap_or_default()
+    }
+    let tags = t
+        .tags
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|tag| tag.name)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    [
+        ("id", t.id.to_string()),
+        ("date", t.date.unwrap_or_default()),
+        ("name", t.name.unwrap_or_default()),
+        ("amount", value_to_money_string(t.amount)),
+        ("category_id", id_string(t.category_id)),
+        ("reviewed", t.is_reviewed.unwrap_or(false).to_string()),
+        ("user_notes", t.user_notes.unwrap_or_default()),
+        ("tags", tags),
+        ("recurring_id", id_string(t.recurring_id)),
+        ("account_id", id_string(t.account_id)),
+        ("item_id", id_string(t.item_id)),
+        ("type", id_string(t.txn_type)),
+    ]
+    .into_iter()
+    .map(|(key, value)| KeyValueRow {
+        key: key.to_string(),
+        value,
+    })
+    .collect()
+}
+
+/// Stable client-side sort; amounts compare numerically, everything else as text.
+fn sort_transactions_by_field(items: &mut [Transaction], field: TransactionField, desc: bool) {
+    fn text(t: &Transaction, field: TransactionField) -> String {
+        match field {
+            TransactionField::Date => t.date.clone().unwrap_or_default(),
+            TransactionField::Name => t.name.clone().unwrap_or_default().to_lowercase(),
+            TransactionField::Category => t
+                .category_id
+                .as_ref()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            TransactionField::Tags => t
+                .tags
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|tag| tag.name.clone())
+                .collect::<Vec<_>>()
+                .join(","),
+            TransactionField::Type => t
+                .txn_type
+                .as_ref()
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            TransactionField::Id => t.id.to_string(),
+            TransactionField::Notes => t.user_notes.clone().unwrap_or_default(),
+            TransactionField::AccountId => t
+                .account_id
+                .as_ref()
+                .map(|a| a.to_string())
+                .unwrap_or_default(),
+            TransactionField::RecurringId => t
+                .recurring_id
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or_default(),
+            TransactionField::Amount
+            | TransactionField::Reviewed
+            | TransactionField::Pending
+            | TransactionField::All => String::new(),
+        }
+    }
+
+    items.sort_by(|a, b| {
+        let ord = match field {
+            TransactionField::Amount => {
+                let amount =
+                    |t: &Transaction| value_to_f64(t.amount.as_ref()).unwrap_or(f64::NEG_INFINITY);
+                amount(a).total_cmp(&amount(b))
+            }
+            TransactionField::Reviewed => a
+                .is_reviewed
+                .unwrap_or(false)
+                .cmp(&b.is_reviewed.unwrap_or(false)),
+            TransactionField::Pending => a
+                .is_pending
+                .unwrap_or(false)
+                .cmp(&b.is_pending.unwrap_or(false)),
+            _ => text(a, field).cmp(&text(b, field)),
+        };
+        if desc { ord.reverse() } else { ord }
+    });
+}
+
 fn render_transactions_table(
     cli: &Cli,
     items: &[Transaction],
     fields: &[TransactionField],
     categories: Option<&HashMap<CategoryId, String>>,
+    out: &mut dyn Write,
 ) -> anyhow::Result<()> {
     use comfy_table::CellAlignment;
 
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_content_arrangement(ContentArrangement::DynamicFullWidth);
-
-    if let Some(w) = terminal_width() {
-        table.set_width(w);
-    }
+    let mut table = new_table();
+    let fields = expand_fields(fields);
 
     let header = fields
         .iter()
@@ -1358,10 +2933,15 @@ fn render_transactions_table(
             TransactionField::Name => header_cell(cli, "name"),
             TransactionField::Amount => header_cell(cli, "amount"),
             TransactionField::Reviewed => header_cell(cli, "reviewed"),
+            TransactionField::Pending => header_cell(cli, "pending"),
             TransactionField::Category => header_cell(cli, "category"),
             TransactionField::Tags => header_cell(cli, "tags"),
             TransactionField::Type => header_cell(cli, "type"),
             TransactionField::Id => header_cell(cli, "id"),
+            TransactionField::Notes => header_cell(cli, "notes"),
+            TransactionField::AccountId => header_cell(cli, "account_id"),
+            TransactionField::RecurringId => header_cell(cli, "recurring_id"),
+            TransactionField::All => unreachable!("expanded above"),
         })
         .collect::<Vec<_>>();
     table.set_header(ComfyRow::from(header));
@@ -1370,7 +2950,7 @@ fn render_transactions_table(
 
     for t in items {
         let mut cells = Vec::new();
-        for f in fields {
+        for f in &fields {
             match f {
                 TransactionField::Date => cells.push(Cell::new(t.date.as_deref().unwrap_or(""))),
                 TransactionField::Name => cells.push(Cell::new(t.name.as_deref().unwrap_or(""))),
@@ -1394,6 +2974,13 @@ fn render_transactions_table(
                     }
                     cells.push(cell);
                 }
+                TransactionField::Pending => {
+                    cells.push(Cell::new(if t.is_pending.unwrap_or(false) {
+                        "pending"
+                    } else {
+                        ""
+                    }))
+                }
                 TransactionField::Category => {
                     let name = t
                         .category_id
@@ -1423,43 +3010,58 @@ fn render_transactions_table(
                         .map(|t| t.to_string())
                         .unwrap_or_default(),
                 )),
-                TransactionField::Id => cells.push(Cell::new(shorten_id_for_table(t.id.as_str()))),
+                TransactionField::Id => {
+                    cells.push(Cell::new(shorten_id_for_table(cli, t.id.as_str())))
+                }
+                TransactionField::Notes => {
+                    cells.push(Cell::new(t.user_notes.as_deref().unwrap_or("")))
+                }
+                TransactionField::AccountId => cells.push(Cell::new(shorten_id_for_table(
+                    cli,
+                    t.account_id.as_ref().map(|a| a.as_str()).unwrap_or(""),
+                ))),
+                TransactionField::RecurringId => cells.push(Cell::new(shorten_id_for_table(
+                    cli,
+                    t.recurring_id.as_ref().map(|r| r.as_str()).unwrap_or(""),
+                ))),
+                TransactionField::All => unreachable!("expanded above"),
             }
         }
         table.add_row(ComfyRow::from(cells));
     }
 
-    println!("{table}");
+    writeln!(out, "{table}")?;
     Ok(())
 }
 
 fn render_transactions_output(
     cli: &Cli,
-    client: &CopilotClient,
+    client: &dyn CopilotClientTrait,
     items: Vec<Transaction>,
     page_info: PageInfo,
     include_page_info: bool,
     fields: &[TransactionField],
+    out: &mut dyn Write,
 ) -> anyhow::Result<()> {
     match cli.output {
         OutputFormat::Json => {
-            let out = TransactionsJsonOutput {
+            let json = TransactionsJsonOutput {
                 transactions: items,
                 page_info: include_page_info.then_some(page_info),
             };
-            let s = serde_json::to_string_pretty(&out)?;
-            println!("{s}");
+            let s = serde_json::to_string_pretty(&json)?;
+            writeln!(out, "{s}")?;
             Ok(())
         }
         OutputFormat::Table => {
-            let cats = if fields.contains(&TransactionField::Category) {
+            let cats = if expand_fields(fields).contains(&TransactionField::Category) {
                 Some(category_name_map(client)?)
             } else {
                 None
             };
-            render_transactions_table(cli, &items, fields, cats.as_ref())?;
+            render_transactions_table(cli, &items, fields, cats.as_ref(), out)?;
             if include_page_info {
-                render_output(
+                render_output_to(
                     cli,
                     vec![
                         KeyValueRow {
@@ -1474,6 +3076,7 @@ fn render_transactions_output(
                                 .unwrap_or_default(),
                         },
                     ],
+                    out,
                 )?;
             }
             Ok(())
@@ -1485,20 +3088,121 @@ fn render_transactions_output(
 mod helper_tests {
     use super::*;
 
+    fn txn(name: &str) -> Transaction {
+        Transaction::builder("txn_1").name(name).build()
+    }
+
+    #[test]
+    fn filter_transactions_query_respects_case_sensitivity() {
+        let insensitive = TransactionFilters {
+            query: Some("amazon"),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_transactions(vec![txn("Amazon.com")], &insensitive).len(),
+            1
+        );
+
+        let sensitive = TransactionFilters {
+            query: Some("amazon"),
+            case_sensitive: true,
+            ..Default::default()
+        };
+        assert!(filter_transactions(vec![txn("Amazon.com")], &sensitive).is_empty());
+        assert_eq!(
+            filter_transactions(vec![txn("amazon fresh")], &sensitive).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn filter_transactions_tag_names_match_case_insensitively() {
+        let tagged = |tag: &str| Transaction::builder("txn_1").tag("tag_1", tag).build();
+        let filter = |tags: &[String]| {
+            filter_transactions(
+                vec![tagged("Shopping")],
+                &TransactionFilters {
+                    tags,
+                    ..Default::default()
+                },
+            )
+            .len()
+        };
+
+        assert_eq!(filter(&["SHOPPING".to_string()]), 1);
+        assert_eq!(filter(&["shopping".to_string()]), 1);
+        assert_eq!(filter(&["Shopping".to_string()]), 1);
+        assert_eq!(filter(&["shop".to_string()]), 0);
+    }
+
+    #[test]
+    fn parse_transaction_field_accepts_names_and_lists_valid_ones() {
+        assert_eq!(parse_transaction_field("date"), Ok(TransactionField::Date));
+        assert_eq!(
+            parse_transaction_field("Amount"),
+            Ok(TransactionField::Amount)
+        );
+        assert_eq!(
+            parse_transaction_field("account"),
+            Ok(TransactionField::AccountId)
+        );
+        assert_eq!(parse_transaction_field("all"), Ok(TransactionField::All));
+
+        let err = parse_transaction_field("merchant").unwrap_err();
+        assert!(err.starts_with("unknown field 'merchant'; valid fields are: date,name,amount,"));
+        assert!(err.ends_with("account-id,recurring-id,all"));
+    }
+
+    #[test]
+    fn append_and_prepend_notes_join_with_a_space() {
+        assert_eq!(append_notes(Some("paid"), "split"), "paid split");
+        assert_eq!(append_notes(Some("paid "), "split"), "paid split");
+        assert_eq!(append_notes(Some(""), "split"), "split");
+        assert_eq!(append_notes(None, "split"), "split");
+        assert_eq!(prepend_notes(Some("paid"), "split"), "split paid");
+        assert_eq!(prepend_notes(Some(" paid"), "split"), "split paid");
+        assert_eq!(prepend_notes(None, "split"), "split");
+    }
+
     #[test]
     fn normalize_date_accepts_yyyy_mm_dd_and_mm_dd_yyyy() {
-        assert_eq!(normalize_date("2025-12-03"), Some("2025-12-03".to_string()));
-        assert_eq!(normalize_date("12-03-2025"), Some("2025-12-03".to_string()));
-        assert_eq!(normalize_date("03-12-2025"), Some("2025-03-12".to_string()));
+        let mdy = |s| normalize_date(s, DateOrder::Mdy);
+        assert_eq!(mdy("2025-12-03"), Some("2025-12-03".to_string()));
+        assert_eq!(mdy("12-03-2025"), Some("2025-12-03".to_string()));
+        assert_eq!(mdy("03-12-2025"), Some("2025-03-12".to_string()));
+        assert_eq!(mdy("01-01-2025"), Some("2025-01-01".to_string()));
+    }
+
+    #[test]
+    fn normalize_date_resolves_ambiguous_dates_by_order() {
+        assert_eq!(
+            normalize_date("04-05-2025", DateOrder::Mdy),
+            Some("2025-04-05".to_string())
+        );
+        assert_eq!(
+            normalize_date("04-05-2025", DateOrder::Dmy),
+            Some("2025-05-04".to_string())
+        );
+        // Year-first input ignores the order.
+        assert_eq!(
+            normalize_date("2025-04-05", DateOrder::Dmy),
+            Some("2025-04-05".to_string())
+        );
+        // No fallback to the other order when the first reading is invalid.
+        assert_eq!(normalize_date("13-01-2025", DateOrder::Mdy), None);
+        assert_eq!(
+            normalize_date("13-01-2025", DateOrder::Dmy),
+            Some("2025-01-13".to_string())
+        );
     }
 
     #[test]
     fn normalize_date_rejects_invalid() {
-        assert_eq!(normalize_date(""), None);
-        assert_eq!(normalize_date("2025-13-01"), None);
-        assert_eq!(normalize_date("2025-00-01"), None);
-        assert_eq!(normalize_date("2025-12-32"), None);
-        assert_eq!(normalize_date("2025/12/01"), None);
+        assert_eq!(normalize_date("", DateOrder::Mdy), None);
+        assert_eq!(normalize_date("2025-13-01", DateOrder::Mdy), None);
+        assert_eq!(normalize_date("2025-00-01", DateOrder::Mdy), None);
+        assert_eq!(normalize_date("2025-12-32", DateOrder::Mdy), None);
+        assert_eq!(normalize_date("2025/12/01", DateOrder::Mdy), None);
     }
 
     #[test]
@@ -1515,6 +3219,13 @@ mod helper_tests {
         assert_eq!(value_to_money_string(None), "");
     }
 
+    #[test]
+    fn money_string_treats_null_string_as_empty() {
+        assert_eq!(value_to_money_string(Some(serde_json::json!("null"))), "");
+        assert_eq!(value_to_money_string(Some(serde_json::json!(" NULL "))), "");
+        assert_eq!(value_to_money_string(Some(serde_json::Value::Null)), "");
+    }
+
     #[test]
     fn sort_to_graphql_maps_values() {
         assert_eq!(
@@ -1540,4 +3251,29 @@ mod helper_tests {
         );
         assert_eq!(build_transactions_filter(false, false), None);
     }
+
+    #[test]
+    fn error_kind_classifies_common_failures() {
+        let kind = |msg: &str| error_kind(&anyhow::anyhow!(msg.to_string()));
+        assert_eq!(kind("unauthenticated (token missing/expired)"), "auth");
+        assert_eq!(kind("transaction not found"), "not-found");
+        assert_eq!(kind("invalid --date-from: \"x\""), "validation");
+        assert_eq!(kind("graphql http error 500"), "general");
+
+        let api = |e: ApiError| error_kind(&anyhow::Error::new(e).context("while listing"));
+        assert_eq!(api(ApiError::Unauthenticated), "auth");
+        // A server-side "not found" message is still an API failure, not a lookup miss.
+        assert_eq!(
+            api(ApiError::GraphqlError {
+                code: None,
+                message: "route not found".to_string(),
+            }),
+            "general"
+        );
+        assert_eq!(
+            exit_code(&anyhow::Error::new(ApiError::Unauthenticated).context("x")),
+            3
+        );
+        assert_eq!(exit_code(&anyhow::anyhow!("transaction not found")), 1);
+    }
 }
@@ -1,76 +1,137 @@
-use comfy_table::Cell;
-use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
 
-use crate::client::{CopilotClient, Recurring};
-use crate::types::{CategoryId, RecurringId};
+use anyhow::Context;
+use clap::ValueEnum;
+use comfy_table::{Cell, CellAlignment, Row as ComfyRow};
+use serde::{Deserialize, Serialize};
 
-use super::render::{KeyValueRow, TableRow, render_output, shorten_id_for_table};
-use super::{Cli, RecurringsCmd, RecurringsListArgs};
+use crate::client::{CopilotClientTrait, Recurring, Transaction};
+use crate::types::{CategoryId, RecurringFrequency, RecurringId, TransactionId};
+
+use super::render::{
+    KeyValueRow, TableRow, header_cell, new_table, render_output, shorten_id_for_table,
+};
+use super::{
+    Cli, OutputFormat, RecurringSort, RecurringsCmd, RecurringsListArgs, TransactionField,
+    value_to_money_string,
+};
 
 pub(super) fn run_recurrings(
     cli: &Cli,
-    client: &CopilotClient,
+    client: &dyn CopilotClientTrait,
     cmd: RecurringsCmd,
 ) -> anyhow::Result<()> {
     match cmd {
         RecurringsCmd::List(args) => {
+            let category_id = super::resolve_category_id(
+                client,
+                args.category_id.as_ref(),
+                args.category.as_deref(),
+            )?;
             let items = client.list_recurrings()?;
-            let items = filter_recurrings(items, &args);
+            let mut items = filter_recurrings(items, category_id.as_ref(), &args);
+            if let Some(sort) = args.sort {
+                sort_recurrings(&mut items, sort, args.sort_desc);
+            }
+            let last_seen = if args.with_last_seen {
+                Some(last_seen_dates(client)?)
+            } else {
+                None
+            };
             let rows = items
                 .into_iter()
-                .map(|r| RecurringRow {
-                    id: r.id,
-                    name: r.name.unwrap_or_default(),
-                    frequency: r.frequency.map(|f| f.to_string()).unwrap_or_default(),
-                    category_id: r.category_id,
+                .map(|r| {
+                    let (min_amount, max_amount) = match (args.with_amount, r.rule) {
+                        (true, Some(rule)) => (
+                            Some(value_to_money_string(rule.min_amount)),
+                            Some(value_to_money_string(rule.max_amount)),
+                        ),
+                        (true, None) => (Some(String::new()), Some(String::new())),
+                        (false, _) => (None, None),
+                    };
+                    RecurringRow {
+                        last_seen_date: last_seen
+                            .as_ref()
+                            .map(|m| m.get(&r.id).cloned().unwrap_or_default()),
+                        id: r.id,
+                        name: r.name.unwrap_or_default(),
+                        frequency: r.frequency.map(|f| f.to_string()).unwrap_or_default(),
+                        category_id: r.category_id,
+                        min_amount,
+                        max_amount,
+                    }
                 })
                 .collect::<Vec<_>>();
-            render_output(cli, rows)
+            let columns = RecurringColumns {
+                amount: args.with_amount,
+                last_seen: args.with_last_seen,
+            };
+            render_recurrings(cli, rows, &columns)
         }
-        RecurringsCmd::Show { id } => {
+        RecurringsCmd::Show(args) => {
             let items = client.list_recurrings()?;
-            let found = items.into_iter().find(|r| r.id == id);
-            match found {
-                Some(r) => render_output(
-                    cli,
-                    vec![
-                        KeyValueRow {
-                            key: "id".to_string(),
-                            value: r.id.to_string(),
-                        },
-                        KeyValueRow {
-                            key: "name".to_string(),
-                            value: r.name.unwrap_or_default(),
-                        },
-                        KeyValueRow {
-                            key: "frequency".to_string(),
-                            value: r.frequency.map(|f| f.to_string()).unwrap_or_default(),
-                        },
-                        KeyValueRow {
-                            key: "category_id".to_string(),
-                            value: r
-                                .category_id
-                                .as_ref()
-                                .map(|c| c.to_string())
-                                .unwrap_or_default(),
-                        },
-                    ],
-                ),
-                None => anyhow::bail!("recurring not found"),
+            let Some(r) = items.into_iter().find(|r| r.id == args.id) else {
+                anyhow::bail!("recurring not found");
+            };
+            let Some(pages) = args.with_transactions else {
+                return render_output(cli, recurring_detail_rows(&r));
+            };
+
+            let (txns, _) = super::fetch_transactions_with_filter_sort(
+                client, 200, None, pages, false, None, None,
+            )?;
+            let txns = txns
+                .into_iter()
+                .filter(|t| t.recurring_id.as_ref() == Some(&r.id))
+                .collect::<Vec<_>>();
+
+            match cli.output {
+                OutputFormat::Json => {
+                    let out = RecurringWithTransactions {
+                        recurring: r,
+                        transactions: txns,
+                    };
+                    writeln!(cli.out.lock(), "{}", serde_json::to_string_pretty(&out)?)?;
+                    Ok(())
+                }
+                OutputFormat::Table => {
+                    render_output(cli, recurring_detail_rows(&r))?;
+                    writeln!(cli.out.lock(), "Recent transactions ({})", txns.len())?;
+                    super::render_transactions_table(
+                        cli,
+                        &txns,
+                        &[
+                            TransactionField::Date,
+                            TransactionField::Name,
+                            TransactionField::Amount,
+                        ],
+                        None,
+                        &mut *cli.out.lock(),
+                    )
+                }
             }
         }
         RecurringsCmd::Create(args) => {
+            let name_note = args
+                .name
+                .as_deref()
+                .map(|n| format!(", name={n:?}"))
+                .unwrap_or_default();
             if cli.dry_run {
-                println!(
-                    "dry-run: would create recurring from transaction {} (frequency={})",
-                    args.transaction_id, args.frequency
-                );
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would create recurring from transaction {} (frequency={}{name_note})",
+                    args.transaction_id,
+                    args.frequency
+                )?;
                 return Ok(());
             }
             super::confirm_write(
                 cli,
                 &format!(
-                    "Create recurring from transaction {} (frequency={})",
+                    "Create recurring from transaction {} (frequency={}{name_note})",
                     args.transaction_id, args.frequency
                 ),
             )?;
@@ -90,6 +151,7 @@ pub(super) fn run_recurrings(
                 &account_id,
                 &txn.id,
                 args.frequency,
+                args.name.as_deref(),
             )?;
 
             render_output(
@@ -122,14 +184,98 @@ pub(super) fn run_recurrings(
                 ],
             )
         }
+        RecurringsCmd::CreateBatch(args) => {
+            let records = read_recurring_batch(&args.file)?;
+            if cli.dry_run {
+                let mut out = cli.out.lock();
+                writeln!(out, "dry-run: would create {} recurring(s):", records.len())?;
+                for r in &records {
+                    let name_note = r
+                        .name
+                        .as_deref()
+                        .map(|n| format!(", name={n:?}"))
+                        .unwrap_or_default();
+                    writeln!(
+                        out,
+                        "  from transaction {} (frequency={}{name_note})",
+                        r.transaction_id, r.frequency
+                    )?;
+                }
+                return Ok(());
+            }
+            super::confirm_write(
+                cli,
+                &format!(
+                    "Create {} recurring(s) from {}",
+                    records.len(),
+                    args.file.display()
+                ),
+            )?;
+
+            let ids = records
+                .iter()
+                .map(|r| r.transaction_id.clone())
+                .collect::<Vec<_>>();
+            let (txns, _) = super::scan_for_transactions(client, &ids)?;
+            let rows = records
+                .into_iter()
+                .map(|r| {
+                    let created = txns
+                        .get(&r.transaction_id)
+                        .ok_or_else(|| anyhow::anyhow!("transaction not found"))
+                        .and_then(|txn| {
+                            let (item_id, account_id) = super::require_item_and_account(txn)?;
+                            client.create_recurring_from_transaction(
+                                &item_id,
+                                &account_id,
+                                &txn.id,
+                                r.frequency,
+                                r.name.as_deref(),
+                            )
+                        });
+                    match created {
+                        Ok(recurring) => RecurringCreateRow {
+                            transaction_id: r.transaction_id,
+                            recurring_id: Some(recurring.id),
+                            name: recurring.name.unwrap_or_default(),
+                            status: "created",
+                            error: None,
+                        },
+                        Err(e) => RecurringCreateRow {
+                            transaction_id: r.transaction_id,
+                            recurring_id: None,
+                            name: r.name.unwrap_or_default(),
+                            status: "failed",
+                            error: Some(format!("{e:#}")),
+                        },
+                    }
+                })
+                .collect::<Vec<_>>();
+            let failed = rows.iter().filter(|r| r.error.is_some()).count();
+            render_output(cli, rows)?;
+            if failed > 0 {
+                anyhow::bail!("failed to create {failed} recurring(s)");
+            }
+            Ok(())
+        }
         RecurringsCmd::Edit(args) => {
+            let rule_json = args
+                .rule_json
+                .as_deref()
+                .map(|s| parse_json_object("--rule-json", s))
+                .transpose()?;
+            let input_json = args
+                .input_json
+                .as_deref()
+                .map(|s| parse_json_object("--input-json", s))
+                .transpose()?;
             if cli.dry_run {
-                println!("dry-run: would edit recurring {}", args.id);
+                writeln!(cli.out.lock(), "dry-run: would edit recurring {}", args.id)?;
                 return Ok(());
             }
             super::confirm_write(cli, &format!("Edit recurring {}", args.id))?;
 
-            let mut rule = serde_json::Map::new();
+            let mut rule = rule_json.unwrap_or_default();
             if let Some(s) = args.name_contains.as_ref() {
                 rule.insert(
                     "nameContains".to_string(),
@@ -149,7 +295,7 @@ pub(super) fn run_recurrings(
                 );
             }
 
-            let mut input = serde_json::Map::new();
+            let mut input = input_json.unwrap_or_default();
             if args.recalculate_only_for_future {
                 input.insert(
                     "recalculateOnlyForFuture".to_string(),
@@ -200,33 +346,256 @@ struct RecurringRow {
     name: String,
     frequency: String,
     category_id: Option<CategoryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_seen_date: Option<String>,
+}
+
+/// Optional columns for `recurrings list` table output.
+#[derive(Debug, Default)]
+struct RecurringColumns {
+    amount: bool,
+    last_seen: bool,
+}
+
+/// Most recent transaction date per recurring, from the first page of transactions.
+fn last_seen_dates(
+    client: &dyn CopilotClientTrait,
+) -> anyhow::Result<HashMap<RecurringId, String>> {
+    let page = client.list_transactions_page(200, None, None, None)?;
+    let mut out: HashMap<RecurringId, String> = HashMap::new();
+    for t in page.transactions {
+        let (Some(id), Some(date)) = (t.recurring_id, t.date) else {
+            continue;
+        };
+        let latest = out.entry(id).or_default();
+        if date > *latest {
+            *latest = date;
+        }
+    }
+    Ok(out)
+}
+
+fn render_recurrings(
+    cli: &Cli,
+    rows: Vec<RecurringRow>,
+    columns: &RecurringColumns,
+) -> anyhow::Result<()> {
+    if cli.output == OutputFormat::Json {
+        return render_output(cli, rows);
+    }
+
+    let mut headers = RecurringRow::HEADERS.to_vec();
+    if columns.amount {
+        headers.extend(["min_amount", "max_amount"]);
+    }
+    if columns.last_seen {
+        headers.push("last_seen_date");
+    }
+
+    let mut table = new_table();
+    table.set_header(ComfyRow::from(
+        headers
+            .iter()
+            .map(|h| header_cell(cli, h))
+            .collect::<Vec<_>>(),
+    ));
+    for row in &rows {
+        let mut cells = row.cells(cli);
+        if columns.amount {
+            for amount in [&row.min_amount, &row.max_amount] {
+                cells.push(
+                    Cell::new(amount.as_deref().unwrap_or("")).set_alignment(CellAlignment::Right),
+                );
+            }
+        }
+        if columns.last_seen {
+            cells.push(Cell::new(row.last_seen_date.as_deref().unwrap_or("")));
+        }
+        table.add_row(ComfyRow::from(cells));
+    }
+    writeln!(cli.out.lock(), "{table}")?;
+    Ok(())
 }
 
 impl TableRow for RecurringRow {
     const HEADERS: &'static [&'static str] = &["id", "name", "frequency", "category_id"];
 
-    fn cells(&self) -> Vec<Cell> {
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
         vec![
-            Cell::new(shorten_id_for_table(self.id.as_str())),
+            Cell::new(shorten_id_for_table(cli, self.id.as_str())),
             Cell::new(&self.name),
             Cell::new(&self.frequency),
             Cell::new(
                 self.category_id
                     .as_ref()
-                    .map(|c| shorten_id_for_table(c.as_str()))
+                    .map(|c| shorten_id_for_table(cli, c.as_str()))
                     .unwrap_or_default(),
             ),
         ]
     }
 }
 
-fn filter_recurrings(mut items: Vec<Recurring>, args: &RecurringsListArgs) -> Vec<Recurring> {
-    if let Some(cat) = args.category_id.as_ref() {
+/// One row of a `recurrings create-batch` CSV.
+#[derive(Debug)]
+struct RecurringBatchRecord {
+    transaction_id: TransactionId,
+    frequency: RecurringFrequency,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRecurringBatchRecord {
+    transaction_id: TransactionId,
+    frequency: String,
+    name: Option<String>,
+}
+
+/// Reads and validates the whole file up front, so a typo fails before anything is created.
+fn read_recurring_batch(path: &Path) -> anyhow::Result<Vec<RecurringBatchRecord>> {
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let mut records = Vec::new();
+    for (i, row) in reader.deserialize::<RawRecurringBatchRecord>().enumerate() {
+        // Line 1 is the header.
+        let line = i + 2;
+        let row = row.with_context(|| format!("{}: line {line}", path.display()))?;
+        // Accept the server's spelling (`BIWEEKLY`) as well as the CLI's (`biweekly`).
+        let frequency = RecurringFrequency::from_str(row.frequency.trim(), true).map_err(|_| {
+            anyhow::anyhow!(
+                "{}: line {line}: unknown frequency {:?}",
+                path.display(),
+                row.frequency
+            )
+        })?;
+        records.push(RecurringBatchRecord {
+            transaction_id: row.transaction_id,
+            frequency,
+            name: row.name.filter(|n| !n.trim().is_empty()),
+        });
+    }
+    if records.is_empty() {
+        anyhow::bail!("{}: no rows", path.display());
+    }
+    Ok(records)
+}
+
+#[derive(Debug, Serialize)]
+struct RecurringCreateRow {
+    transaction_id: TransactionId,
+    recurring_id: Option<RecurringId>,
+    name: String,
+    status: &'static str,
+    error: Option<String>,
+}
+
+impl TableRow for RecurringCreateRow {
+    const HEADERS: &'static [&'static str] =
+        &["transaction_id", "recurring_id", "name", "status", "error"];
+
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
+        vec![
+            Cell::new(shorten_id_for_table(cli, self.transaction_id.as_str())),
+            Cell::new(
+                self.recurring_id
+                    .as_ref()
+                    .map(|id| shorten_id_for_table(cli, id.as_str()))
+                    .unwrap_or_default(),
+            ),
+            Cell::new(&self.name),
+            Cell::new(self.status),
+            Cell::new(self.error.as_deref().unwrap_or_default()),
+        ]
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RecurringWithTransactions {
+    recurring: Recurring,
+    transactions: Vec<Transaction>,
+}
+
+fn parse_json_object(
+    flag: &str,
+    s: &str,
+) -> anyhow::Result<serde_json::Map<String, serde_json::Value>> {
+    match serde_json::from_str(s).with_context(|| format!("failed to parse {flag}"))? {
+        serde_json::Value::Object(map) => Ok(map),
+        _ => anyhow::bail!("{flag} must be a JSON object"),
+    }
+}
+
+fn recurring_detail_rows(r: &Recurring) -> Vec<KeyValueRow> {
+    vec![
+        KeyValueRow {
+            key: "id".to_string(),
+            value: r.id.to_string(),
+        },
+        KeyValueRow {
+            key: "name".to_string(),
+            value: r.name.clone().unwrap_or_default(),
+        },
+        KeyValueRow {
+            key: "frequency".to_string(),
+            value: r
+                .frequency
+                .as_ref()
+                .map(|f| f.to_string())
+                .unwrap_or_default(),
+        },
+        KeyValueRow {
+            key: "category_id".to_string(),
+            value: r
+                .category_id
+                .as_ref()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+        },
+    ]
+}
+
+/// Stable sort; `desc` flips only the sort key, so ties stay in ascending name order.
+fn sort_recurrings(items: &mut [Recurring], sort: RecurringSort, desc: bool) {
+    let name = |r: &Recurring| r.name.as_deref().unwrap_or("").to_lowercase();
+    let key = |r: &Recurring| match sort {
+        RecurringSort::Name => Some(name(r)),
+        RecurringSort::Frequency => Some(r.frequency.map(|f| f.to_string()).unwrap_or_default()),
+        RecurringSort::CategoryId => r.category_id.as_ref().map(|c| c.to_string()),
+    };
+    items.sort_by(|a, b| {
+        let ord = key(a).cmp(&key(b));
+        let ord = if desc { ord.reverse() } else { ord };
+        ord.then_with(|| name(a).cmp(&name(b)))
+    });
+}
+
+fn filter_recurrings(
+    mut items: Vec<Recurring>,
+    category_id: Option<&CategoryId>,
+    args: &RecurringsListArgs,
+) -> Vec<Recurring> {
+    if let Some(cat) = category_id {
         items.retain(|r| r.category_id.as_ref() == Some(cat));
     }
+    if args.no_category {
+        items.retain(|r| r.category_id.is_none());
+    }
+    if args.has_category {
+        items.retain(|r| r.category_id.is_some());
+    }
     if let Some(q) = args.name_contains.as_ref() {
         let q = q.to_lowercase();
         items.retain(|r| r.name.as_deref().unwrap_or("").to_lowercase().contains(&q));
     }
+    if let Some(want) = args.name_exact.as_ref() {
+        let want = want.to_lowercase();
+        items.retain(|r| r.name.as_deref().unwrap_or("").to_lowercase() == want);
+    }
+    if let Some(freq) = args.frequency_not {
+        items.retain(|r| r.frequency != Some(freq));
+    }
     items
 }
@@ -1,3 +1,8 @@
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use anyhow::Context;
 use comfy_table::modifiers::UTF8_ROUND_CORNERS;
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::{Attribute, Cell, Color, ContentArrangement, Row as ComfyRow, Table};
@@ -15,7 +20,7 @@ pub(super) struct KeyValueRow {
 
 pub(super) trait TableRow {
     const HEADERS: &'static [&'static str];
-    fn cells(&self) -> Vec<Cell>;
+    fn cells(&self, cli: &Cli) -> Vec<Cell>;
 }
 
 pub(super) fn terminal_width() -> Option<u16> {
@@ -27,8 +32,11 @@ pub(super) fn terminal_width() -> Option<u16> {
     terminal_size().map(|(TermWidth(w), _)| w)
 }
 
-pub(super) fn shorten_id_for_table(id: &str) -> String {
+pub(super) fn shorten_id_for_table(cli: &Cli, id: &str) -> String {
     let id = id.trim();
+    if cli.no_truncate_ids {
+        return id.to_string();
+    }
     let max = 18usize;
     if id.is_empty() || id.width() <= max {
         return id.to_string();
@@ -45,24 +53,23 @@ pub(super) fn shorten_id_for_table(id: &str) -> String {
 pub(super) fn render_output<T: Serialize + TableRow>(
     cli: &Cli,
     rows: Vec<T>,
+) -> anyhow::Result<()> {
+    render_output_to(cli, rows, &mut *cli.out.lock())
+}
+
+pub(super) fn render_output_to<T: Serialize + TableRow>(
+    cli: &Cli,
+    rows: Vec<T>,
+    out: &mut dyn Write,
 ) -> anyhow::Result<()> {
     match cli.output {
         OutputFormat::Json => {
             let s = serde_json::to_string_pretty(&rows)?;
-            println!("{s}");
+            writeln!(out, "{s}")?;
             Ok(())
         }
         OutputFormat::Table => {
-            let mut table = Table::new();
-            table
-                .load_preset(UTF8_FULL)
-                .apply_modifier(UTF8_ROUND_CORNERS)
-                .set_content_arrangement(ContentArrangement::DynamicFullWidth);
-
-            if let Some(w) = terminal_width() {
-                table.set_width(w);
-            }
-
+            let mut table = new_table();
             table.set_header(ComfyRow::from(
                 T::HEADERS
                     .iter()
@@ -70,14 +77,80 @@ pub(super) fn render_output<T: Serialize + TableRow>(
                     .collect::<Vec<_>>(),
             ));
             for row in rows {
-                table.add_row(ComfyRow::from(row.cells()));
+                table.add_row(ComfyRow::from(row.cells(cli)));
             }
-            println!("{table}");
+            writeln!(out, "{table}")?;
             Ok(())
         }
     }
 }
 
+/// Where command output goes: stdout, or the file given by `--output-file`/`--append-output-file`.
+///
+/// Errors, warnings, and prompts always go to stderr.
+#[derive(Clone)]
+pub(crate) struct OutputSink(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl OutputSink {
+    /// `None` or `-` means stdout.
+    pub(super) fn open(path: Option<&Path>) -> anyhow::Result<Self> {
+        let w: Box<dyn Write + Send> = match path {
+            Some(p) if p.as_os_str() != "-" => {
+                let file = std::fs::File::create(p)
+                    .with_context(|| format!("failed to create {}", p.display()))?;
+                Box::new(std::io::BufWriter::new(file))
+            }
+            _ => Box::new(std::io::stdout()),
+        };
+        Ok(Self(Arc::new(Mutex::new(w))))
+    }
+
+    /// Appends to `path`, creating it if needed (`-` means stdout).
+    pub(super) fn append(path: &Path) -> anyhow::Result<Self> {
+        if path.as_os_str() == "-" {
+            return Self::open(None);
+        }
+        let file = std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        Ok(Self(Arc::new(Mutex::new(Box::new(
+            std::io::BufWriter::new(file),
+        )))))
+    }
+
+    pub(super) fn lock(&self) -> MutexGuard<'_, Box<dyn Write + Send>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for OutputSink {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(Box::new(std::io::stdout()))))
+    }
+}
+
+impl std::fmt::Debug for OutputSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OutputSink")
+    }
+}
+
+/// A table with the shared preset, sized to the terminal when known.
+pub(super) fn new_table() -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::DynamicFullWidth);
+
+    if let Some(w) = terminal_width() {
+        table.set_width(w);
+    }
+    table
+}
+
 pub(super) fn header_cell(cli: &Cli, text: &str) -> Cell {
     if super::should_color(cli) {
         Cell::new(text)
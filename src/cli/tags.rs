@@ -1,57 +1,91 @@
-use comfy_table::Cell;
+use std::collections::HashMap;
+
+use comfy_table::{Cell, CellAlignment, Row as ComfyRow};
 use serde::Serialize;
 
-use crate::client::CopilotClient;
+use crate::client::{CopilotClientTrait, Tag};
+use crate::types::TagId;
 
-use super::render::{KeyValueRow, TableRow, render_output, shorten_id_for_table};
-use super::{Cli, TagsCmd};
+use super::render::{
+    KeyValueRow, TableRow, header_cell, new_table, render_output, shorten_id_for_table,
+};
+use super::{Cli, OutputFormat, TagSort, TagsCmd};
 
-pub(super) fn run_tags(cli: &Cli, client: &CopilotClient, cmd: TagsCmd) -> anyhow::Result<()> {
+pub(super) fn run_tags(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    cmd: TagsCmd,
+) -> anyhow::Result<()> {
     match cmd {
-        TagsCmd::List => {
-            let items = client.list_tags()?;
-            let rows = items
+        TagsCmd::List(args) => {
+            if args.sort == Some(TagSort::Count) && !args.with_counts {
+                anyhow::bail!("--sort count requires --with-counts");
+            }
+            let mut items = client.list_tags()?;
+            if let Some(color) = args.color_name_filter.as_deref() {
+                items.retain(|t| {
+                    t.color_name
+                        .as_deref()
+                        .is_some_and(|c| c.eq_ignore_ascii_case(color))
+                });
+            }
+            if args.no_color_name {
+                items.retain(|t| t.color_name.is_none());
+            }
+            let counts = if args.with_counts {
+                Some(count_tag_usage(client, args.all)?)
+            } else {
+                None
+            };
+            let mut rows = items
                 .into_iter()
                 .map(|t| TagRow {
+                    count: counts.as_ref().map(|c| c.get(&t.id).copied().unwrap_or(0)),
                     id: t.id,
                     name: t.name.unwrap_or_default(),
                     color_name: t.color_name.unwrap_or_default(),
                 })
                 .collect::<Vec<_>>();
-            render_output(cli, rows)
+            let default_sort = if args.with_counts {
+                TagSort::Count
+            } else {
+                TagSort::Name
+            };
+            sort_tags(&mut rows, args.sort.unwrap_or(default_sort));
+            render_tags(cli, rows, args.with_counts)
         }
         TagsCmd::Create(args) => {
+            if args.if_not_exists {
+                let existing = client.list_tags()?.into_iter().find(|t| {
+                    t.name
+                        .as_deref()
+                        .is_some_and(|n| n.eq_ignore_ascii_case(&args.name))
+                });
+                if let Some(tag) = existing {
+                    eprintln!("note: tag {:?} already exists; not creating", args.name);
+                    return render_output(cli, tag_detail_rows(tag));
+                }
+            }
             if cli.dry_run {
-                println!("dry-run: would create tag: {}", args.name);
+                writeln!(cli.out.lock(), "dry-run: would create tag: {}", args.name)?;
                 return Ok(());
             }
             super::confirm_write(cli, &format!("Create tag: {}", args.name))?;
             let tag = client.create_tag(&args.name, args.color_name.as_deref())?;
-            render_output(
-                cli,
-                vec![
-                    KeyValueRow {
-                        key: "id".to_string(),
-                        value: tag.id.to_string(),
-                    },
-                    KeyValueRow {
-                        key: "name".to_string(),
-                        value: tag.name.unwrap_or_default(),
-                    },
-                    KeyValueRow {
-                        key: "color_name".to_string(),
-                        value: tag.color_name.unwrap_or_default(),
-                    },
-                ],
-            )
+            render_output(cli, tag_detail_rows(tag))
         }
         TagsCmd::Delete(args) => {
+            let id = match (args.id, args.name.as_deref()) {
+                (Some(id), _) => id,
+                (None, Some(name)) => find_tag_id_by_name(client, name)?,
+                (None, None) => unreachable!("clap requires an id or --name"),
+            };
             if cli.dry_run {
-                println!("dry-run: would delete tag {}", args.id);
+                writeln!(cli.out.lock(), "dry-run: would delete tag {id}")?;
                 return Ok(());
             }
-            super::confirm_write(cli, &format!("Delete tag {}", args.id))?;
-            let ok = client.delete_tag(&args.id)?;
+            super::confirm_write(cli, &format!("Delete tag {id}"))?;
+            let ok = client.delete_tag(&id)?;
             render_output(
                 cli,
                 vec![KeyValueRow {
@@ -60,22 +94,195 @@ pub(super) fn run_tags(cli: &Cli, client: &CopilotClient, cmd: TagsCmd) -> anyho
                 }],
             )
         }
+        TagsCmd::BulkDelete(args) => {
+            let ids = args
+                .ids
+                .iter()
+                .map(|id| id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            if cli.dry_run {
+                writeln!(
+                    cli.out.lock(),
+                    "dry-run: would delete {} tag(s): {ids}",
+                    args.ids.len()
+                )?;
+                return Ok(());
+            }
+            super::confirm_write(cli, &format!("Delete {} tag(s): {ids}", args.ids.len()))?;
+
+            let rows = args
+                .ids
+                .into_iter()
+                .map(|id| match client.delete_tag(&id) {
+                    Ok(deleted) => TagDeleteRow {
+                        id,
+                        deleted,
+                        error: None,
+                    },
+                    Err(e) => TagDeleteRow {
+                        id,
+                        deleted: false,
+                        error: Some(format!("{e:#}")),
+                    },
+                })
+                .collect::<Vec<_>>();
+            let failed = rows.iter().filter(|r| !r.deleted).count();
+            render_output(cli, rows)?;
+            if failed > 0 {
+                anyhow::bail!("failed to delete {failed} tag(s)");
+            }
+            Ok(())
+        }
     }
 }
 
+#[derive(Debug, Serialize)]
+struct TagDeleteRow {
+    id: TagId,
+    deleted: bool,
+    error: Option<String>,
+}
+
+impl TableRow for TagDeleteRow {
+    const HEADERS: &'static [&'static str] = &["id", "deleted", "error"];
+
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
+        vec![
+            Cell::new(shorten_id_for_table(cli, self.id.as_str())),
+            Cell::new(self.deleted.to_string()),
+            Cell::new(self.error.as_deref().unwrap_or_default()),
+        ]
+    }
+}
+
+fn tag_detail_rows(tag: Tag) -> Vec<KeyValueRow> {
+    vec![
+        KeyValueRow {
+            key: "id".to_string(),
+            value: tag.id.to_string(),
+        },
+        KeyValueRow {
+            key: "name".to_string(),
+            value: tag.name.unwrap_or_default(),
+        },
+        KeyValueRow {
+            key: "color_name".to_string(),
+            value: tag.color_name.unwrap_or_default(),
+        },
+    ]
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct TagRow {
-    id: crate::types::TagId,
+    id: TagId,
     name: String,
     color_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<usize>,
+}
+
+/// Counts tag usage over the most recent 1000 transactions (or all of them).
+fn count_tag_usage(
+    client: &dyn CopilotClientTrait,
+    all: bool,
+) -> anyhow::Result<HashMap<TagId, usize>> {
+    let (txns, _) =
+        super::fetch_transactions_with_filter_sort(client, 200, None, 5, all, None, None)?;
+    let mut counts = HashMap::new();
+    for t in &txns {
+        for tag in t.tags.as_deref().unwrap_or_default() {
+            *counts.entry(tag.id.clone()).or_insert(0) += 1;
+        }
+    }
+    Ok(counts)
+}
+
+fn find_tag_id_by_name(client: &dyn CopilotClientTrait, name: &str) -> anyhow::Result<TagId> {
+    tag_id_by_name(&client.list_tags()?, name)
+}
+
+/// Resolves each tag name (case-insensitive) to its id, with a single `Tags` fetch.
+pub(super) fn resolve_tag_ids_by_names(
+    client: &dyn CopilotClientTrait,
+    names: &[String],
+) -> anyhow::Result<Vec<TagId>> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tags = client.list_tags()?;
+    names
+        .iter()
+        .map(|name| tag_id_by_name(&tags, name))
+        .collect()
+}
+
+fn tag_id_by_name(tags: &[Tag], name: &str) -> anyhow::Result<TagId> {
+    let ids = tags
+        .iter()
+        .filter(|t| {
+            t.name
+                .as_deref()
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+        .map(|t| &t.id)
+        .collect::<Vec<_>>();
+    match ids.as_slice() {
+        [] => anyhow::bail!("no tag named {name:?}"),
+        [id] => Ok((*id).clone()),
+        many => anyhow::bail!(
+            "tag name {name:?} is ambiguous ({}); pass the tag id instead",
+            many.iter()
+                .map(|id| id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn sort_tags(rows: &mut [TagRow], sort: TagSort) {
+    match sort {
+        TagSort::Name => rows.sort_by_key(|r| r.name.to_lowercase()),
+        TagSort::Color => rows.sort_by(|a, b| {
+            (&a.color_name, a.name.to_lowercase()).cmp(&(&b.color_name, b.name.to_lowercase()))
+        }),
+        TagSort::Count => rows.sort_by_key(|r| std::cmp::Reverse(r.count)),
+        TagSort::ApiOrder => {}
+    }
+}
+
+fn render_tags(cli: &Cli, rows: Vec<TagRow>, with_counts: bool) -> anyhow::Result<()> {
+    if cli.output == OutputFormat::Json || !with_counts {
+        return render_output(cli, rows);
+    }
+
+    let mut headers = TagRow::HEADERS.to_vec();
+    headers.push("count");
+
+    let mut table = new_table();
+    table.set_header(ComfyRow::from(
+        headers
+            .iter()
+            .map(|h| header_cell(cli, h))
+            .collect::<Vec<_>>(),
+    ));
+    for row in &rows {
+        let mut cells = row.cells(cli);
+        cells.push(
+            Cell::new(row.count.unwrap_or(0).to_string()).set_alignment(CellAlignment::Right),
+        );
+        table.add_row(ComfyRow::from(cells));
+    }
+    writeln!(cli.out.lock(), "{table}")?;
+    Ok(())
 }
 
 impl TableRow for TagRow {
     const HEADERS: &'static [&'static str] = &["id", "name", "color_name"];
 
-    fn cells(&self) -> Vec<Cell> {
+    fn cells(&self, cli: &Cli) -> Vec<Cell> {
         vec![
-            Cell::new(shorten_id_for_table(self.id.as_str())),
+            Cell::new(shorten_id_for_table(cli, self.id.as_str())),
             Cell::new(&self.name),
             Cell::new(&self.color_name),
         ]
@@ -0,0 +1,191 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{CopilotClientTrait, PageInfo, Transaction};
+use crate::config::watch_state_path;
+use crate::types::TransactionId;
+
+use super::{Cli, WatchArgs};
+
+const PAGE_SIZE: usize = 100;
+
+/// The newest transaction date seen so far, plus every id seen on that date (ids carry no
+/// ordering, so a second transaction on the same day is only "new" if its id is unknown).
+///
+/// Also the value behind `transactions list --since-cursor` (see [`WatchMarker::parse_cursor`]).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct WatchMarker {
+    date: String,
+    ids: Vec<TransactionId>,
+}
+
+impl WatchMarker {
+    /// Parses `YYYY-MM-DD` or `YYYY-MM-DD:<id>,<id>...`. A bare date counts everything on
+    /// that day as new, which is how a first `--since-cursor` run starts.
+    pub(super) fn parse_cursor(s: &str) -> anyhow::Result<Self> {
+        let (date, ids) = s.split_once(':').unwrap_or((s, ""));
+        let valid = date.len() == 10
+            && date.char_indices().all(|(i, c)| match i {
+                4 | 7 => c == '-',
+                _ => c.is_ascii_digit(),
+            });
+        if !valid {
+            anyhow::bail!("invalid --since-cursor {s:?} (expected YYYY-MM-DD[:<id>,<id>...])");
+        }
+        Ok(Self {
+            date: date.to_string(),
+            ids: ids
+                .split(',')
+                .filter(|id| !id.is_empty())
+                .map(TransactionId::from)
+                .collect(),
+        })
+    }
+
+    /// The `--since-cursor` value that resumes after everything seen so far.
+    pub(super) fn to_cursor(&self) -> String {
+        if self.ids.is_empty() {
+            return self.date.clone();
+        }
+        let ids = self
+            .ids
+            .iter()
+            .map(|id| id.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{}:{ids}", self.date)
+    }
+
+    fn is_new(&self, t: &Transaction) -> bool {
+        match t.date.as_deref() {
+            Some(d) => d > self.date.as_str() || (d == self.date && !self.ids.contains(&t.id)),
+            None => false,
+        }
+    }
+
+    pub(super) fn advance(&mut self, txns: &[Transaction]) {
+        for t in txns {
+            let Some(d) = t.date.as_deref() else {
+                continue;
+            };
+            if d > self.date.as_str() {
+                self.date = d.to_string();
+                self.ids.clear();
+            }
+            if d == self.date && !self.ids.contains(&t.id) {
+                self.ids.push(t.id.clone());
+            }
+        }
+    }
+}
+
+pub(super) fn run_watch(
+    cli: &Cli,
+    client: &dyn CopilotClientTrait,
+    args: WatchArgs,
+) -> anyhow::Result<()> {
+    let state_file = args.state_file.clone().unwrap_or_else(watch_state_path);
+    // `--run-once` picks up where the previous run stopped; a long-running watch starts fresh.
+    let mut marker = if args.run_once {
+        load_marker(&state_file)?
+    } else {
+        None
+    };
+
+    loop {
+        let items = poll(client, &mut marker)?;
+        if !items.is_empty() {
+            let mut out = cli.out.lock();
+            super::render_transactions_output(
+                cli,
+                client,
+                items,
+                PageInfo::default(),
+                false,
+                &args.fields,
+                &mut *out,
+            )?;
+            out.flush()?;
+        }
+
+        if args.run_once {
+            if let Some(m) = marker.as_ref() {
+                save_marker(&state_file, m)?;
+            }
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_secs(args.interval));
+    }
+}
+
+/// Re-reads the newest transactions and returns those newer than `marker`, advancing it.
+///
+/// Without a marker this only records where "now" is: nothing is returned, so the first poll
+/// doesn't replay history.
+fn poll(
+    client: &dyn CopilotClientTrait,
+    marker: &mut Option<WatchMarker>,
+) -> anyhow::Result<Vec<Transaction>> {
+    let Some(m) = marker.as_mut() else {
+        let page = client.list_transactions_page(PAGE_SIZE, None, None, None)?;
+        let mut fresh = WatchMarker::default();
+        fresh.advance(&page.transactions);
+        *marker = Some(fresh);
+        return Ok(Vec::new());
+    };
+    let out = fetch_newer(client, m, PAGE_SIZE)?;
+    m.advance(&out);
+    Ok(out)
+}
+
+/// Reads the feed from its head (newest first, no server filter or sort) and returns every
+/// transaction newer than `marker`, stopping at the first page that holds an older one.
+pub(super) fn fetch_newer(
+    client: &dyn CopilotClientTrait,
+    marker: &WatchMarker,
+    page_size: usize,
+) -> anyhow::Result<Vec<Transaction>> {
+    let mut out = Vec::new();
+    let mut after = None;
+    loop {
+        let page = client.list_transactions_page(page_size, after, None, None)?;
+        let total = page.transactions.len();
+        let fresh = page
+            .transactions
+            .into_iter()
+            .filter(|t| marker.is_new(t))
+            .collect::<Vec<_>>();
+        // Newest first: once a page holds something already seen, older pages hold nothing new.
+        let all_new = fresh.len() == total;
+        out.extend(fresh);
+        match page.page_info.end_cursor {
+            Some(c) if all_new && page.page_info.has_next_page.unwrap_or(false) => {
+                after = Some(c);
+            }
+            _ => break,
+        }
+    }
+    Ok(out)
+}
+
+fn load_marker(path: &Path) -> anyhow::Result<Option<WatchMarker>> {
+    match std::fs::read_to_string(path) {
+        Ok(s) => serde_json::from_str(&s)
+            .map(Some)
+            .with_context(|| format!("malformed watch state {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+fn save_marker(path: &Path, marker: &WatchMarker) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(marker)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -19,10 +20,162 @@ pub enum ClientMode {
         token: Option<String>,
         token_file: PathBuf,
         session_dir: Option<PathBuf>,
+        /// Extra attempts after a network error or 5xx response (0 = no retries).
+        max_retries: u32,
+        /// Backoff before retry `n` (0-based) is `retry_delay * 2^n`.
+        retry_delay: Duration,
+        /// Whole-request timeout (default: `COPILOT_HTTP_TIMEOUT_SECS`, else 60s).
+        timeout: Option<Duration>,
+        /// Timeout for establishing the connection only (default:
+        /// `COPILOT_HTTP_CONNECT_TIMEOUT_SECS`, else 10s).
+        connect_timeout: Option<Duration>,
     },
     Fixtures(PathBuf),
 }
 
+/// Failure of a single GraphQL request.
+///
+/// Client methods return `anyhow::Result`, so callers that need to tell failures apart
+/// downcast to this (`err.downcast_ref::<ApiError>()`).
+#[derive(Debug)]
+pub enum ApiError {
+    /// The server rejected the token (and a session refresh, if configured, didn't help).
+    Unauthenticated,
+    /// The response carried a GraphQL `errors` entry.
+    GraphqlError {
+        code: Option<String>,
+        message: String,
+    },
+    /// Non-2xx response without a GraphQL error body.
+    HttpError {
+        status: u16,
+    },
+    NetworkError(reqwest::Error),
+    /// Fixture mode: no `<Operation>.json` response for this operation.
+    FixtureMissing(PathBuf),
+    /// Fixture mode: the response file isn't valid JSON.
+    InvalidFixture(PathBuf, serde_json::Error),
+    /// The token expired and refreshing it from the browser session failed.
+    TokenRefresh(anyhow::Error),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Unauthenticated => f.write_str(
+                "unauthenticated (token missing/expired). Re-run `copilot auth login` (or `copilot auth set-token`).",
+            ),
+            ApiError::GraphqlError { code, message } => {
+                f.write_str("graphql error")?;
+                if let Some(c) = code {
+                    write!(f, " ({c})")?;
+                }
+                if !message.is_empty() {
+                    write!(f, ": {message}")?;
+                }
+                Ok(())
+            }
+            ApiError::HttpError { status } => write!(f, "graphql http error {status}"),
+            ApiError::NetworkError(e) => write!(f, "request failed: {e}"),
+            ApiError::FixtureMissing(path) => {
+                write!(f, "fixture not found: {}", path.display())
+            }
+            ApiError::InvalidFixture(path, e) => {
+                write!(f, "invalid fixture {}: {e}", path.display())
+            }
+            ApiError::TokenRefresh(e) => write!(f, "token refresh failed: {e:#}"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(e: reqwest::Error) -> Self {
+        ApiError::NetworkError(e)
+    }
+}
+
+/// The API surface the CLI talks to.
+///
+/// `CopilotClient` is the real implementation; tests can swap in an in-memory one and drive
+/// the CLI through [`crate::cli::run_with_client`].
+pub trait CopilotClientTrait {
+    fn try_user_query(&self) -> anyhow::Result<()>;
+
+    /// The signed-in user's id and email (from the separate `UserInfo` query, which may fail
+    /// even when `try_user_query` succeeds).
+    fn get_user_info(&self) -> anyhow::Result<UserInfo>;
+
+    fn list_transactions(&self, limit: usize) -> anyhow::Result<Vec<Transaction>> {
+        Ok(self
+            .list_transactions_page(limit, None, None, None)?
+            .transactions)
+    }
+
+    fn list_transactions_page(
+        &self,
+        first: usize,
+        after: Option<String>,
+        filter: Option<Value>,
+        sort: Option<Value>,
+    ) -> anyhow::Result<TransactionsPage>;
+
+    fn list_categories(
+        &self,
+        spend: bool,
+        budget: bool,
+        rollovers: bool,
+    ) -> anyhow::Result<Vec<Category>>;
+
+    fn list_recurrings(&self) -> anyhow::Result<Vec<Recurring>>;
+
+    fn list_tags(&self) -> anyhow::Result<Vec<Tag>>;
+
+    fn list_accounts(&self) -> anyhow::Result<Vec<Account>>;
+
+    fn list_budget_months(&self) -> anyhow::Result<Vec<BudgetMonth>>;
+
+    fn bulk_edit_transactions_reviewed(
+        &self,
+        ids: Vec<TransactionIdRef>,
+        is_reviewed: bool,
+    ) -> anyhow::Result<BulkEditTransactionsResult>;
+
+    fn edit_transaction(
+        &self,
+        item_id: &ItemId,
+        account_id: &AccountId,
+        id: &TransactionId,
+        input: Value,
+    ) -> anyhow::Result<Transaction>;
+
+    fn add_transaction_to_recurring(
+        &self,
+        item_id: &ItemId,
+        account_id: &AccountId,
+        id: &TransactionId,
+        recurring_id: &RecurringId,
+    ) -> anyhow::Result<Transaction>;
+
+    fn delete_tag(&self, id: &TagId) -> anyhow::Result<bool>;
+
+    fn create_tag(&self, name: &str, color_name: Option<&str>) -> anyhow::Result<Tag>;
+
+    fn create_category(&self, input: Value, spend: bool, budget: bool) -> anyhow::Result<Category>;
+
+    fn create_recurring_from_transaction(
+        &self,
+        item_id: &ItemId,
+        account_id: &AccountId,
+        transaction_id: &TransactionId,
+        frequency: RecurringFrequency,
+        name: Option<&str>,
+    ) -> anyhow::Result<Recurring>;
+
+    fn edit_recurring(&self, id: &RecurringId, input: Value) -> anyhow::Result<Recurring>;
+}
+
 #[derive(Debug, Clone)]
 pub struct CopilotClient {
     mode: ClientMode,
@@ -32,19 +185,24 @@ impl CopilotClient {
     pub fn new(mode: ClientMode) -> Self {
         Self { mode }
     }
+}
 
-    pub fn try_user_query(&self) -> anyhow::Result<()> {
+impl CopilotClientTrait for CopilotClient {
+    fn try_user_query(&self) -> anyhow::Result<()> {
         let _ = self.graphql("User", ops::USER, json!({}))?;
         Ok(())
     }
 
-    pub fn list_transactions(&self, limit: usize) -> anyhow::Result<Vec<Transaction>> {
-        Ok(self
-            .list_transactions_page(limit, None, None, None)?
-            .transactions)
+    fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        let data = self.graphql("UserInfo", ops::USER_INFO, json!({}))?;
+        let user = data
+            .pointer("/data/user")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("unexpected UserInfo response shape"))?;
+        Ok(serde_json::from_value(user)?)
     }
 
-    pub fn list_transactions_page(
+    fn list_transactions_page(
         &self,
         first: usize,
         after: Option<String>,
@@ -68,10 +226,16 @@ impl CopilotClient {
             .ok_or_else(|| anyhow::anyhow!("unexpected Transactions response shape"))?;
 
         let mut transactions = Vec::new();
+        let mut cursors = Vec::new();
         for edge in edges {
             if let Some(node) = edge.pointer("/node") {
                 let t: Transaction = serde_json::from_value(node.clone())?;
                 transactions.push(t);
+                cursors.push(
+                    edge.get("cursor")
+                        .and_then(|c| c.as_str())
+                        .map(str::to_string),
+                );
             }
         }
 
@@ -83,11 +247,12 @@ impl CopilotClient {
 
         Ok(TransactionsPage {
             transactions,
+            cursors,
             page_info,
         })
     }
 
-    pub fn list_categories(
+    fn list_categories(
         &self,
         spend: bool,
         budget: bool,
@@ -116,7 +281,7 @@ impl CopilotClient {
         Ok(out)
     }
 
-    pub fn list_recurrings(&self) -> anyhow::Result<Vec<Recurring>> {
+    fn list_recurrings(&self) -> anyhow::Result<Vec<Recurring>> {
         let data = self.graphql("Recurrings", ops::RECURRINGS, json!({ "filter": null }))?;
         let items = data
             .pointer("/data/recurrings")
@@ -131,7 +296,7 @@ impl CopilotClient {
         Ok(out)
     }
 
-    pub fn list_tags(&self) -> anyhow::Result<Vec<Tag>> {
+    fn list_tags(&self) -> anyhow::Result<Vec<Tag>> {
         let data = self.graphql("Tags", ops::TAGS, json!({}))?;
         let items = data
             .pointer("/data/tags")
@@ -146,7 +311,26 @@ impl CopilotClient {
         Ok(out)
     }
 
-    pub fn list_budget_months(&self) -> anyhow::Result<Vec<BudgetMonth>> {
+    fn list_accounts(&self) -> anyhow::Result<Vec<Account>> {
+        let data = self.graphql(
+            "Accounts",
+            ops::ACCOUNTS,
+            json!({ "filter": null, "accountLink": false }),
+        )?;
+        let items = data
+            .pointer("/data/accounts")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow::anyhow!("unexpected Accounts response shape"))?;
+
+        let mut out = Vec::new();
+        for item in items {
+            let a: Account = serde_json::from_value(item.clone())?;
+            out.push(a);
+        }
+        Ok(out)
+    }
+
+    fn list_budget_months(&self) -> anyhow::Result<Vec<BudgetMonth>> {
         let data = self.graphql("Budgets", ops::BUDGETS, json!({}))?;
         let histories = data
             .pointer("/data/categoriesTotal/budget/histories")
@@ -169,7 +353,7 @@ impl CopilotClient {
         Ok(out)
     }
 
-    pub fn bulk_edit_transactions_reviewed(
+    fn bulk_edit_transactions_reviewed(
         &self,
         ids: Vec<TransactionIdRef>,
         is_reviewed: bool,
@@ -214,7 +398,7 @@ impl CopilotClient {
         })
     }
 
-    pub fn edit_transaction(
+    fn edit_transaction(
         &self,
         item_id: &ItemId,
         account_id: &AccountId,
@@ -239,7 +423,7 @@ impl CopilotClient {
         Ok(serde_json::from_value(txn)?)
     }
 
-    pub fn add_transaction_to_recurring(
+    fn add_transaction_to_recurring(
         &self,
         item_id: &ItemId,
         account_id: &AccountId,
@@ -269,7 +453,7 @@ impl CopilotClient {
         Ok(serde_json::from_value(txn)?)
     }
 
-    pub fn delete_tag(&self, id: &TagId) -> anyhow::Result<bool> {
+    fn delete_tag(&self, id: &TagId) -> anyhow::Result<bool> {
         let data = self.graphql(
             "DeleteTag",
             ops::DELETE_TAG,
@@ -285,7 +469,7 @@ impl CopilotClient {
         Ok(v)
     }
 
-    pub fn create_tag(&self, name: &str, color_name: Option<&str>) -> anyhow::Result<Tag> {
+    fn create_tag(&self, name: &str, color_name: Option<&str>) -> anyhow::Result<Tag> {
         let data = self.graphql(
             "CreateTag",
             ops::CREATE_TAG,
@@ -304,12 +488,7 @@ impl CopilotClient {
         Ok(serde_json::from_value(tag)?)
     }
 
-    pub fn create_category(
-        &self,
-        input: Value,
-        spend: bool,
-        budget: bool,
-    ) -> anyhow::Result<Category> {
+    fn create_category(&self, input: Value, spend: bool, budget: bool) -> anyhow::Result<Category> {
         let data = self.graphql(
             "CreateCategory",
             ops::CREATE_CATEGORY,
@@ -327,26 +506,29 @@ impl CopilotClient {
         Ok(serde_json::from_value(cat)?)
     }
 
-    pub fn create_recurring_from_transaction(
+    fn create_recurring_from_transaction(
         &self,
         item_id: &ItemId,
         account_id: &AccountId,
         transaction_id: &TransactionId,
         frequency: RecurringFrequency,
+        name: Option<&str>,
     ) -> anyhow::Result<Recurring> {
+        let mut input = json!({
+            "frequency": frequency,
+            "transaction": {
+                "accountId": account_id.as_str(),
+                "itemId": item_id.as_str(),
+                "transactionId": transaction_id.as_str()
+            }
+        });
+        if let Some(name) = name {
+            input["name"] = json!(name);
+        }
         let data = self.graphql(
             "CreateRecurring",
             ops::CREATE_RECURRING,
-            json!({
-                "input": {
-                    "frequency": frequency,
-                    "transaction": {
-                        "accountId": account_id.as_str(),
-                        "itemId": item_id.as_str(),
-                        "transactionId": transaction_id.as_str()
-                    }
-                }
-            }),
+            json!({ "input": input }),
         )?;
 
         let recurring = data
@@ -356,7 +538,7 @@ impl CopilotClient {
         Ok(serde_json::from_value(recurring)?)
     }
 
-    pub fn edit_recurring(&self, id: &RecurringId, input: Value) -> anyhow::Result<Recurring> {
+    fn edit_recurring(&self, id: &RecurringId, input: Value) -> anyhow::Result<Recurring> {
         let data = self.graphql(
             "EditRecurring",
             ops::EDIT_RECURRING,
@@ -372,91 +554,227 @@ impl CopilotClient {
             .ok_or_else(|| anyhow::anyhow!("unexpected EditRecurring response shape"))?;
         Ok(serde_json::from_value(recurring)?)
     }
+}
+
+impl CopilotClient {
+    /// Runs several operations and returns their responses in order.
+    ///
+    /// With the `batch-queries` feature the operations go out as one HTTP request carrying a
+    /// JSON array; if the server doesn't answer with an array of matching length (i.e. it
+    /// doesn't support batching), they're re-sent one at a time.
+    pub fn graphql_batch(&self, ops: &[(&str, &str, Value)]) -> anyhow::Result<Vec<Value>> {
+        #[cfg(feature = "batch-queries")]
+        if ops.len() > 1 && matches!(self.mode, ClientMode::Http { .. }) {
+            if let Some(responses) = self.try_batched(ops)? {
+                return Ok(responses);
+            }
+            eprintln!(
+                "note: server rejected a batched request; sending {} operations sequentially",
+                ops.len()
+            );
+        }
+
+        ops.iter()
+            .map(|(name, query, variables)| Ok(self.graphql(name, query, variables.clone())?))
+            .collect()
+    }
+
+    /// `Ok(None)` means the server didn't understand the batch and the caller should fall back.
+    #[cfg(feature = "batch-queries")]
+    fn try_batched(&self, ops: &[(&str, &str, Value)]) -> Result<Option<Vec<Value>>, ApiError> {
+        let payload = Value::Array(
+            ops.iter()
+                .map(|(name, query, variables)| {
+                    json!({
+                        "operationName": name,
+                        "query": query,
+                        "variables": variables
+                    })
+                })
+                .collect(),
+        );
+        let label = ops
+            .iter()
+            .map(|(name, _, _)| *name)
+            .collect::<Vec<_>>()
+            .join("+");
+
+        let idempotent = !ops.iter().any(|(_, query, _)| is_mutation(query));
+        let body = match self.post_graphql(&label, idempotent, &payload) {
+            Ok(body) => body,
+            Err(ApiError::HttpError { status }) if (400..500).contains(&status) => return Ok(None),
+            Err(ApiError::GraphqlError { .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let Value::Array(responses) = body else {
+            return Ok(None);
+        };
+        if responses.len() != ops.len() {
+            return Ok(None);
+        }
+        responses
+            .into_iter()
+            .map(|r| match graphql_error(&r) {
+                Some(err) => Err(err),
+                None => Ok(r),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
 
     fn graphql(
         &self,
         operation_name: &str,
         query: &str,
         variables: Value,
-    ) -> anyhow::Result<Value> {
+    ) -> Result<Value, ApiError> {
         match &self.mode {
             ClientMode::Fixtures(dir) => {
                 let path = dir.join(format!("{operation_name}.json"));
-                let s = fs::read_to_string(&path)?;
-                Ok(serde_json::from_str(&s)?)
+                let Ok(s) = fs::read_to_string(&path) else {
+                    return Err(ApiError::FixtureMissing(path));
+                };
+                serde_json::from_str(&s).map_err(|e| ApiError::InvalidFixture(path, e))
             }
-            ClientMode::Http {
-                base_url,
-                token,
-                token_file,
-                session_dir,
-            } => {
-                let url = format!("{}/api/graphql", base_url.trim_end_matches('/'));
-                let http = http_client_from_env()?;
-
-                let mut current_token = token.clone().or_else(|| load_token(token_file).ok());
-
-                for attempt in 1..=2 {
-                    let mut req = http.post(&url).json(&json!({
-                        "operationName": operation_name,
-                        "query": query,
-                        "variables": variables
-                    }));
-                    if let Some(t) = current_token.as_ref() {
-                        req = req.bearer_auth(t);
-                    }
-
-                    let resp = req.send()?;
-                    let status = resp.status();
-                    let body: Value = resp.json()?;
-
-                    if is_unauthenticated(&body) {
-                        if attempt == 1
-                            && let Some(dir) = session_dir.as_ref().filter(|d| d.exists())
-                        {
-                            let refreshed = refresh_token_via_session(dir, 180)?;
-                            save_token(token_file, &refreshed)?;
-                            current_token = Some(refreshed);
-                            continue;
-                        }
-                        anyhow::bail!(
-                            "unauthenticated (token missing/expired). Re-run `copilot auth login` (or `copilot auth set-token`)."
-                        );
-                    }
-
-                    if let Some(msg) = format_graphql_error(&body) {
-                        anyhow::bail!("{msg}");
-                    }
-
-                    if !status.is_success() {
-                        anyhow::bail!("graphql http error {status}");
-                    }
-                    return Ok(body);
+            ClientMode::Http { .. } => self.post_graphql(
+                operation_name,
+                !is_mutation(query),
+                &json!({
+                    "operationName": operation_name,
+                    "query": query,
+                    "variables": variables
+                }),
+            ),
+        }
+    }
+
+    /// POSTs `payload` to the GraphQL endpoint, with retries and a one-shot token refresh.
+    /// `label` only names the request in retry notes.
+    ///
+    /// Timeouts and 5xx responses are only retried when `idempotent`: a mutation may already
+    /// have been applied, so resending it could apply it twice.
+    fn post_graphql(
+        &self,
+        label: &str,
+        idempotent: bool,
+        payload: &Value,
+    ) -> Result<Value, ApiError> {
+        let ClientMode::Http {
+            base_url,
+            token,
+            token_file,
+            session_dir,
+            max_retries,
+            retry_delay,
+            timeout,
+            connect_timeout,
+        } = &self.mode
+        else {
+            unreachable!("post_graphql is only called in HTTP mode");
+        };
+
+        let url = format!("{}/api/graphql", base_url.trim_end_matches('/'));
+        let http = http_client(*timeout, *connect_timeout)?;
+
+        let mut current_token = token.clone().or_else(|| load_token(token_file).ok());
+        let mut refreshed = false;
+        let mut retries = 0;
+        let mut backoff = |why: &dyn fmt::Display| {
+            if retries >= *max_retries {
+                return false;
+            }
+            let delay = *retry_delay * 2u32.saturating_pow(retries);
+            retries += 1;
+            eprintln!(
+                "note: {label} failed ({why}); retrying in {}ms ({retries}/{max_retries})",
+                delay.as_millis()
+            );
+            std::thread::sleep(delay);
+            true
+        };
+
+        loop {
+            let mut req = http.post(&url).json(payload);
+            if let Some(t) = current_token.as_ref() {
+                req = req.bearer_auth(t);
+            }
+
+            let resp = match req.send() {
+                Ok(resp) => resp,
+                // A failed connect never reached the server, so even a mutation is safe to resend.
+                Err(e) if (idempotent || e.is_connect()) && backoff(&e) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let status = resp.status();
+            if idempotent && status.is_server_error() && backoff(&status) {
+                continue;
+            }
+            let body: Value = match resp.json() {
+                Ok(body) => body,
+                // e.g. a proxy's HTML 502 page: the status is all there is to report.
+                Err(_) if !status.is_success() => {
+                    return Err(ApiError::HttpError {
+                        status: status.as_u16(),
+                    });
                 }
+                Err(e) => return Err(e.into()),
+            };
+
+            if is_unauthenticated(&body) {
+                if !refreshed && let Some(dir) = session_dir.as_ref().filter(|d| d.exists()) {
+                    let t = refresh_token_via_session(dir, 180)
+                        .and_then(|t| save_token(token_file, &t).map(|()| t))
+                        .map_err(ApiError::TokenRefresh)?;
+                    current_token = Some(t);
+                    refreshed = true;
+                    continue;
+                }
+                return Err(ApiError::Unauthenticated);
+            }
+
+            if let Some(err) = graphql_error(&body) {
+                return Err(err);
+            }
 
-                unreachable!("loop returns or errors")
+            if !status.is_success() {
+                return Err(ApiError::HttpError {
+                    status: status.as_u16(),
+                });
             }
+            return Ok(body);
         }
     }
 }
 
-fn http_client_from_env() -> anyhow::Result<reqwest::blocking::Client> {
-    let timeout_secs: u64 = std::env::var("COPILOT_HTTP_TIMEOUT_SECS")
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(60);
-    let connect_timeout_secs: u64 = std::env::var("COPILOT_HTTP_CONNECT_TIMEOUT_SECS")
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(10);
+/// Whether a GraphQL document's operation is a mutation (as opposed to a query).
+fn is_mutation(query: &str) -> bool {
+    query.trim_start().starts_with("mutation")
+}
+
+fn http_client(
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+) -> reqwest::Result<reqwest::blocking::Client> {
+    let from_env = |var: &str, default_secs: u64| {
+        let secs = std::env::var(var)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_secs);
+        Duration::from_secs(secs)
+    };
 
-    Ok(reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(timeout_secs))
-        .connect_timeout(Duration::from_secs(connect_timeout_secs))
-        .build()?)
+    reqwest::blocking::Client::builder()
+        .timeout(timeout.unwrap_or_else(|| from_env("COPILOT_HTTP_TIMEOUT_SECS", 60)))
+        .connect_timeout(
+            connect_timeout.unwrap_or_else(|| from_env("COPILOT_HTTP_CONNECT_TIMEOUT_SECS", 10)),
+        )
+        .build()
 }
 
 fn is_unauthenticated(body: &Value) -> bool {
+    if let Some(responses) = body.as_array() {
+        return responses.iter().any(is_unauthenticated);
+    }
     body.get("errors")
         .and_then(|e| e.as_array())
         .and_then(|arr| arr.first())
@@ -466,7 +784,7 @@ fn is_unauthenticated(body: &Value) -> bool {
         == Some("UNAUTHENTICATED")
 }
 
-fn format_graphql_error(body: &Value) -> Option<String> {
+fn graphql_error(body: &Value) -> Option<ApiError> {
     let errors = body.get("errors")?.as_array()?;
     let first = errors.first()?;
     let message = first.get("message").and_then(|m| m.as_str()).unwrap_or("");
@@ -479,15 +797,10 @@ fn format_graphql_error(body: &Value) -> Option<String> {
         return None;
     }
 
-    let mut out = String::new();
-    out.push_str("graphql error");
-    if let Some(c) = code {
-        out.push_str(&format!(" ({c})"));
-    }
-    if !message.is_empty() {
-        out.push_str(&format!(": {message}"));
-    }
-    Some(out)
+    Some(ApiError::GraphqlError {
+        code: code.map(str::to_string),
+        message: message.to_string(),
+    })
 }
 
 fn refresh_token_via_session(session_dir: &Path, timeout_seconds: u64) -> anyhow::Result<String> {
@@ -521,7 +834,7 @@ fn refresh_token_via_session(session_dir: &Path, timeout_seconds: u64) -> anyhow
     Ok(token)
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct PageInfo {
     #[serde(rename = "endCursor")]
     pub end_cursor: Option<String>,
@@ -536,10 +849,36 @@ pub struct PageInfo {
 #[derive(Debug)]
 pub struct TransactionsPage {
     pub transactions: Vec<Transaction>,
+    /// Edge cursor for each entry in `transactions` (same order).
+    pub cursors: Vec<Option<String>>,
     pub page_info: PageInfo,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserInfo {
+    pub id: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Account {
+    pub id: AccountId,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub account_type: Option<String>,
+    #[serde(rename = "subType")]
+    pub sub_type: Option<String>,
+    #[serde(rename = "institutionId")]
+    pub institution_id: Option<String>,
+    pub mask: Option<String>,
+    pub balance: Option<Value>,
+    #[serde(rename = "isUserClosed")]
+    pub is_user_closed: Option<bool>,
+    #[serde(rename = "isUserHidden")]
+    pub is_user_hidden: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Tag {
     pub id: TagId,
     pub name: Option<String>,
@@ -547,7 +886,7 @@ pub struct Tag {
     pub color_name: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Transaction {
     pub id: TransactionId,
     pub date: Option<String>,
@@ -559,6 +898,8 @@ pub struct Transaction {
     pub txn_type: Option<TransactionType>,
     #[serde(rename = "isReviewed")]
     pub is_reviewed: Option<bool>,
+    #[serde(rename = "isPending")]
+    pub is_pending: Option<bool>,
     #[serde(rename = "categoryId")]
     pub category_id: Option<CategoryId>,
     #[serde(rename = "accountId")]
@@ -570,6 +911,112 @@ pub struct Transaction {
     pub tags: Option<Vec<Tag>>,
 }
 
+impl Transaction {
+    /// Starts a transaction with only an id; every other field stays `None` unless set.
+    pub fn builder(id: impl Into<TransactionId>) -> TransactionBuilder {
+        TransactionBuilder {
+            txn: Transaction {
+                id: id.into(),
+                date: None,
+                name: None,
+                amount: None,
+                item_id: None,
+                txn_type: None,
+                is_reviewed: None,
+                is_pending: None,
+                category_id: None,
+                account_id: None,
+                recurring_id: None,
+                user_notes: None,
+                tags: None,
+            },
+        }
+    }
+}
+
+/// Builds [`Transaction`] values without spelling out every optional field (handy in tests).
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    txn: Transaction,
+}
+
+impl TransactionBuilder {
+    pub fn id(&mut self, id: impl Into<TransactionId>) -> &mut Self {
+        self.txn.id = id.into();
+        self
+    }
+
+    pub fn date(&mut self, date: impl Into<String>) -> &mut Self {
+        self.txn.date = Some(date.into());
+        self
+    }
+
+    pub fn name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.txn.name = Some(name.into());
+        self
+    }
+
+    /// The API sends amounts as strings (e.g. `"-57.48"`), but any JSON value is accepted.
+    pub fn amount(&mut self, amount: impl Into<Value>) -> &mut Self {
+        self.txn.amount = Some(amount.into());
+        self
+    }
+
+    pub fn item_id(&mut self, id: impl Into<ItemId>) -> &mut Self {
+        self.txn.item_id = Some(id.into());
+        self
+    }
+
+    pub fn txn_type(&mut self, txn_type: TransactionType) -> &mut Self {
+        self.txn.txn_type = Some(txn_type);
+        self
+    }
+
+    pub fn reviewed(&mut self, reviewed: bool) -> &mut Self {
+        self.txn.is_reviewed = Some(reviewed);
+        self
+    }
+
+    pub fn pending(&mut self, pending: bool) -> &mut Self {
+        self.txn.is_pending = Some(pending);
+        self
+    }
+
+    pub fn category_id(&mut self, id: impl Into<CategoryId>) -> &mut Self {
+        self.txn.category_id = Some(id.into());
+        self
+    }
+
+    pub fn account_id(&mut self, id: impl Into<AccountId>) -> &mut Self {
+        self.txn.account_id = Some(id.into());
+        self
+    }
+
+    pub fn recurring_id(&mut self, id: impl Into<RecurringId>) -> &mut Self {
+        self.txn.recurring_id = Some(id.into());
+        self
+    }
+
+    pub fn user_notes(&mut self, notes: impl Into<String>) -> &mut Self {
+        self.txn.user_notes = Some(notes.into());
+        self
+    }
+
+    /// Adds one tag (by id and name), keeping any already added.
+    pub fn tag(&mut self, id: impl Into<TagId>, name: impl Into<String>) -> &mut Self {
+        self.txn.tags.get_or_insert_with(Vec::new).push(Tag {
+            id: id.into(),
+            name: Some(name.into()),
+            color_name: None,
+        });
+        self
+    }
+
+    pub fn build(&self) -> Transaction {
+        self.txn.clone()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TransactionIdRef {
     #[serde(rename = "accountId")]
@@ -579,8 +1026,10 @@ pub struct TransactionIdRef {
     pub item_id: ItemId,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct BulkEditFailed {
+    #[serde(default)]
+    pub transaction: Option<Transaction>,
     pub error: Option<String>,
     #[serde(rename = "errorCode")]
     pub error_code: Option<String>,
@@ -592,7 +1041,7 @@ pub struct BulkEditTransactionsResult {
     pub failed: Vec<BulkEditFailed>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "__typename")]
 pub enum Icon {
     EmojiUnicode {
@@ -606,7 +1055,7 @@ pub enum Icon {
     Other,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Category {
     pub id: CategoryId,
     pub name: Option<String>,
@@ -621,17 +1070,45 @@ pub struct Category {
     #[serde(rename = "colorName")]
     pub color_name: Option<String>,
     pub icon: Option<Icon>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spend: Option<CategoryPeriods>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<CategoryPeriods>,
     #[serde(rename = "childCategories")]
     pub child_categories: Option<Vec<Category>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// `spend` / `budget` on a category: the current month plus history.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CategoryPeriods {
+    pub current: Option<CategoryMonthly>,
+    pub histories: Option<Vec<CategoryMonthly>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CategoryMonthly {
+    pub month: Option<String>,
+    pub amount: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Recurring {
     pub id: RecurringId,
     pub name: Option<String>,
     pub frequency: Option<RecurringFrequency>,
     #[serde(rename = "categoryId")]
     pub category_id: Option<CategoryId>,
+    pub rule: Option<RecurringRule>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecurringRule {
+    #[serde(rename = "nameContains")]
+    pub name_contains: Option<String>,
+    #[serde(rename = "minAmount")]
+    pub min_amount: Option<Value>,
+    #[serde(rename = "maxAmount")]
+    pub max_amount: Option<Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -639,3 +1116,50 @@ pub struct BudgetMonth {
     pub month: String,
     pub amount: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transaction_builder_leaves_unset_fields_empty() {
+        let txn = Transaction::builder("txn_1").build();
+        assert_eq!(txn.id.as_str(), "txn_1");
+        assert!(txn.name.is_none());
+        assert!(txn.amount.is_none());
+        assert!(txn.is_reviewed.is_none());
+        assert!(txn.tags.is_none());
+    }
+
+    #[test]
+    fn transaction_builder_sets_fields_and_serializes_like_the_api() {
+        let txn = Transaction::builder("txn_1")
+            .id("txn_2")
+            .date("2025-12-15")
+            .name("Amazon.com")
+            .amount("-57.48")
+            .reviewed(true)
+            .pending(false)
+            .category_id("cat_shops")
+            .tag("tag_shopping", "Shopping")
+            .build();
+        assert_eq!(
+            serde_json::to_value(&txn).unwrap(),
+            json!({
+                "id": "txn_2",
+                "date": "2025-12-15",
+                "name": "Amazon.com",
+                "amount": "-57.48",
+                "itemId": null,
+                "type": null,
+                "isReviewed": true,
+                "isPending": false,
+                "categoryId": "cat_shops",
+                "accountId": null,
+                "recurringId": null,
+                "userNotes": null,
+                "tags": [{ "id": "tag_shopping", "name": "Shopping", "colorName": null }],
+            })
+        );
+    }
+}
@@ -2,6 +2,13 @@ use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use anyhow::Context;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::cli::{ColorMode, OutputFormat};
+
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
@@ -23,6 +30,123 @@ pub fn session_path() -> PathBuf {
     p
 }
 
+pub fn watch_state_path() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    let mut p = PathBuf::from(home);
+    p.push(".config");
+    p.push("copilot-money-cli");
+    p.push("watch-state.json");
+    p
+}
+
+pub fn config_path() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    let mut p = PathBuf::from(home);
+    p.push(".config");
+    p.push("copilot-money-cli");
+    p.push("config.toml");
+    p
+}
+
+/// Defaults from `config.toml`. Flags given on the command line always win.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub output: Option<OutputFormat>,
+    pub color: Option<ColorMode>,
+    #[serde(default)]
+    pub transactions: TransactionsConfig,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionsConfig {
+    #[serde(default)]
+    pub list: TransactionsListConfig,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionsListConfig {
+    /// Default `--page-size` for `transactions list`.
+    pub limit: Option<usize>,
+}
+
+/// Loads the config file as an editable document (empty if it doesn't exist yet).
+pub fn load_config_document(path: &Path) -> anyhow::Result<DocumentMut> {
+    if !path.exists() {
+        return Ok(DocumentMut::new());
+    }
+    let s = fs::read_to_string(path)
+        .with_context(|| format!("failed to read config file {}", path.display()))?;
+    s.parse::<DocumentMut>()
+        .with_context(|| format!("invalid TOML in config file {}", path.display()))
+}
+
+/// JSON Schema describing `config.toml`.
+pub fn config_json_schema() -> serde_json::Value {
+    schemars::schema_for!(Config).to_value()
+}
+
+pub fn parse_config(doc: &DocumentMut) -> anyhow::Result<Config> {
+    Ok(toml_edit::de::from_str(&doc.to_string())?)
+}
+
+pub fn load_config(path: &Path) -> anyhow::Result<Config> {
+    parse_config(&load_config_document(path)?)
+}
+
+/// Looks up a dotted key (`transactions.list.limit`).
+pub fn config_get<'a>(doc: &'a DocumentMut, key: &str) -> Option<&'a Item> {
+    let mut item = doc.as_item();
+    for part in key.split('.') {
+        item = item.get(part)?;
+    }
+    (!item.is_none()).then_some(item)
+}
+
+/// Sets a dotted key, creating parent tables as needed, and checks the result still
+/// deserializes as a [`Config`] (so unknown keys and ill-typed values are rejected).
+///
+/// `value` is parsed as a TOML value when possible (`50`, `true`), otherwise stored as a string.
+pub fn config_set(doc: &mut DocumentMut, key: &str, value: &str) -> anyhow::Result<()> {
+    let parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|p| p.trim().is_empty()) {
+        anyhow::bail!("invalid config key: {key:?}");
+    }
+    let (last, parents) = parts.split_last().expect("split always yields a part");
+
+    let mut updated = doc.clone();
+    let mut item = updated.as_item_mut();
+    for part in parents {
+        if item.get(part).is_none() {
+            let mut t = Table::new();
+            t.set_implicit(true);
+            item[*part] = Item::Table(t);
+        }
+        item = &mut item[*part];
+        if !item.is_table_like() {
+            anyhow::bail!("invalid config key {key:?}: {part:?} is not a table");
+        }
+    }
+    let parsed = value
+        .parse::<toml_edit::Value>()
+        .unwrap_or_else(|_| toml_edit::Value::from(value));
+    item[*last] = Item::Value(parsed);
+
+    parse_config(&updated).with_context(|| format!("invalid value for {key}: {value:?}"))?;
+    *doc = updated;
+    Ok(())
+}
+
+pub fn save_config_document(path: &Path, doc: &DocumentMut) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, doc.to_string())?;
+    Ok(())
+}
+
 pub fn load_token(path: &Path) -> anyhow::Result<String> {
     let s = fs::read_to_string(path)?;
     let t = s.trim().to_string();
@@ -56,6 +180,11 @@ pub fn ensure_private_dir(path: &Path) -> anyhow::Result<()> {
 pub fn token_helper_path() -> Option<PathBuf> {
     let mut candidates: Vec<PathBuf> = Vec::new();
 
+    // Explicit override (also used by tests to stub the helper).
+    if let Some(p) = std::env::var_os("COPILOT_TOKEN_HELPER") {
+        candidates.push(PathBuf::from(p));
+    }
+
     // Dev/test path (only exists in a source checkout).
     candidates.push(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tools/get_token.py"));
 
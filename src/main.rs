@@ -1,8 +1,17 @@
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 
-use copilot_money_cli::cli::Cli;
+use copilot_money_cli::cli::{Cli, ErrorFormat};
 
-fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
-    copilot_money_cli::cli::run(cli)
+fn main() {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    copilot_money_cli::cli::apply_config_defaults(&mut cli, &matches);
+    let format_errors = cli.format_errors;
+    if let Err(e) = copilot_money_cli::cli::run(cli) {
+        match format_errors {
+            Some(ErrorFormat::Json) => eprintln!("{}", copilot_money_cli::cli::error_json(&e)),
+            None => eprintln!("Error: {e:?}"),
+        }
+        std::process::exit(copilot_money_cli::cli::exit_code(&e));
+    }
 }
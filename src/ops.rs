@@ -1,4 +1,10 @@
 pub const USER: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/graphql/User.graphql"));
+/// Just the user's id and email. Not a captured web-app document (`User` doesn't select
+/// `email`), so callers treat a failure as "unknown" rather than as a bad token.
+pub const USER_INFO: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/graphql/UserInfo.graphql"
+));
 pub const TRANSACTIONS: &str = include_str!(concat!(
     env!("CARGO_MANIFEST_DIR"),
     "/graphql/Transactions.graphql"
@@ -12,6 +18,10 @@ pub const RECURRINGS: &str = include_str!(concat!(
     "/graphql/Recurrings.graphql"
 ));
 pub const TAGS: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/graphql/Tags.graphql"));
+pub const ACCOUNTS: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/graphql/Accounts.graphql"
+));
 pub const BUDGETS: &str = include_str!(concat!(
     env!("CARGO_MANIFEST_DIR"),
     "/graphql/Budgets.graphql"
@@ -31,8 +31,14 @@ pub struct FieldDef {
 pub struct SchemaDraft {
     pub objects: BTreeMap<String, BTreeMap<String, FieldDef>>,
     pub inputs: BTreeSet<String>,
+    /// Fields of input types, inferred from object literals passed as arguments.
+    pub input_fields: BTreeMap<String, BTreeMap<String, TypeRef>>,
     pub unions: BTreeMap<String, BTreeSet<String>>,
     pub scalars: BTreeSet<String>,
+    /// Report conflicting field types instead of widening them to `JSON`.
+    pub strict: bool,
+    /// Conflicts seen in strict mode, e.g. `field Transaction.amount has conflicting types: String vs Float`.
+    pub conflicts: BTreeSet<String>,
 }
 
 impl SchemaDraft {
@@ -45,11 +51,22 @@ impl SchemaDraft {
 
         let fields = self.objects.entry(object.to_string()).or_default();
         match fields.get_mut(field_name) {
-            Some(existing) => {
-                if existing.ty != ty {
-                    existing.ty = TypeRef::named("JSON");
+            Some(existing) if existing.ty == ty => {}
+            Some(existing) if self.strict => {
+                // `JSON` means "not known yet" (e.g. the placeholder `add_field_arg` inserts),
+                // so only two concrete types count as a conflict.
+                let json = TypeRef::named("JSON");
+                if existing.ty == json {
+                    existing.ty = ty;
+                } else if ty != json {
+                    self.conflicts.insert(format!(
+                        "field {object}.{field_name} has conflicting types: {} vs {}",
+                        render_type_ref(&existing.ty),
+                        render_type_ref(&ty)
+                    ));
                 }
             }
+            Some(existing) => existing.ty = TypeRef::named("JSON"),
             None => {
                 fields.insert(
                     field_name.to_string(),
@@ -63,6 +80,21 @@ impl SchemaDraft {
         }
     }
 
+    pub fn add_input_field(&mut self, input: &str, field_name: &str, ty: TypeRef) {
+        self.inputs.insert(input.to_string());
+        let fields = self.input_fields.entry(input.to_string()).or_default();
+        match fields.get_mut(field_name) {
+            Some(existing) => {
+                if *existing != ty {
+                    *existing = TypeRef::named("JSON");
+                }
+            }
+            None => {
+                fields.insert(field_name.to_string(), ty);
+            }
+        }
+    }
+
     pub fn add_field_arg(&mut self, object: &str, field_name: &str, arg_name: &str, ty: TypeRef) {
         self.ensure_object(object);
         let fields = self.objects.entry(object.to_string()).or_default();
@@ -88,6 +120,15 @@ impl SchemaDraft {
 }
 
 pub fn render_schema_from_operations(graphql_files: &[PathBuf]) -> anyhow::Result<String> {
+    render_schema_from_operations_with(graphql_files, false)
+}
+
+/// Like [`render_schema_from_operations`], but with `strict` set, fails listing every field
+/// whose operations disagree on its type instead of widening it to `JSON`.
+pub fn render_schema_from_operations_with(
+    graphql_files: &[PathBuf],
+    strict: bool,
+) -> anyhow::Result<String> {
     let mut sources = Vec::new();
     for p in graphql_files {
         sources.push((p.clone(), fs::read_to_string(p)?));
@@ -115,7 +156,11 @@ pub fn render_schema_from_operations(graphql_files: &[PathBuf]) -> anyhow::Resul
         }
     }
 
-    let mut draft = SchemaDraft::default();
+    let mut draft = SchemaDraft {
+        strict,
+        ..SchemaDraft::default()
+    };
+    let mut literals = Vec::new();
     draft.scalars.insert("JSON".to_string());
     draft.ensure_object("Query");
     draft.ensure_object("Mutation");
@@ -131,6 +176,7 @@ pub fn render_schema_from_operations(graphql_files: &[PathBuf]) -> anyhow::Resul
                     &q.selection_set,
                     &fragments,
                     &var_types,
+                    &mut literals,
                 );
             }
             OperationDefinition::Mutation(m) => {
@@ -142,6 +188,7 @@ pub fn render_schema_from_operations(graphql_files: &[PathBuf]) -> anyhow::Resul
                     &m.selection_set,
                     &fragments,
                     &var_types,
+                    &mut literals,
                 );
             }
             OperationDefinition::Subscription(s) => {
@@ -153,10 +200,18 @@ pub fn render_schema_from_operations(graphql_files: &[PathBuf]) -> anyhow::Resul
                     &s.selection_set,
                     &fragments,
                     &var_types,
+                    &mut literals,
                 );
             }
             OperationDefinition::SelectionSet(ss) => {
-                process_selection_set(&mut draft, "Query", ss, &fragments, &HashMap::new());
+                process_selection_set(
+                    &mut draft,
+                    "Query",
+                    ss,
+                    &fragments,
+                    &HashMap::new(),
+                    &mut literals,
+                );
             }
         }
     }
@@ -170,9 +225,16 @@ pub fn render_schema_from_operations(graphql_files: &[PathBuf]) -> anyhow::Resul
             &frag.selection_set,
             &fragments,
             &HashMap::new(),
+            &mut literals,
         );
     }
 
+    resolve_input_literals(&mut draft, literals);
+
+    if !draft.conflicts.is_empty() {
+        let list = draft.conflicts.iter().cloned().collect::<Vec<_>>();
+        anyhow::bail!("ambiguous types:\n{}", list.join("\n"));
+    }
     Ok(render_schema(&draft, &sources))
 }
 
@@ -230,7 +292,16 @@ fn render_schema(draft: &SchemaDraft, sources: &[(PathBuf, String)]) -> String {
     }
 
     for input_name in &draft.inputs {
-        out.push_str(&format!("input {input_name} {{\n  _stub: JSON\n}}\n\n"));
+        match draft.input_fields.get(input_name) {
+            Some(fields) if !fields.is_empty() => {
+                out.push_str(&format!("input {input_name} {{\n"));
+                for (field_name, ty) in fields {
+                    out.push_str(&format!("  {field_name}: {}\n", render_type_ref(ty)));
+                }
+                out.push_str("}\n\n");
+            }
+            _ => out.push_str(&format!("input {input_name} {{\n  _stub: JSON\n}}\n\n")),
+        }
     }
 
     out
@@ -291,9 +362,10 @@ fn process_selection_set(
     selection_set: &SelectionSet<String>,
     fragments: &HashMap<String, FragmentDefinition<String>>,
     var_types: &HashMap<String, TypeRef>,
+    literals: &mut Vec<InputLiteral>,
 ) {
     if draft.unions.contains_key(current_type) {
-        process_union_selection_set(draft, selection_set, fragments, var_types);
+        process_union_selection_set(draft, selection_set, fragments, var_types, literals);
         return;
     }
 
@@ -308,6 +380,24 @@ fn process_selection_set(
 
                 // Capture argument names and best-effort types.
                 for (arg_name, value) in &field.arguments {
+                    // Object literals name an input type we only know after all operations are
+                    // seen (another operation may pass a typed variable to the same argument).
+                    if let Value::Object(obj) = value {
+                        literals.push(InputLiteral {
+                            object: current_type.to_string(),
+                            field: field.name.clone(),
+                            arg: arg_name.clone(),
+                            fields: obj
+                                .iter()
+                                .map(|(k, v)| {
+                                    let ty = infer_argument_type(v, var_types)
+                                        .unwrap_or_else(|| TypeRef::named("JSON"));
+                                    (k.clone(), ty)
+                                })
+                                .collect(),
+                        });
+                        continue;
+                    }
                     if let Some(arg_ty) = infer_argument_type(value, var_types) {
                         draft.add_field_arg(current_type, &field.name, arg_name, arg_ty);
                     }
@@ -331,6 +421,7 @@ fn process_selection_set(
                             &field.selection_set,
                             fragments,
                             var_types,
+                            literals,
                         );
                     } else {
                         process_selection_set(
@@ -339,6 +430,7 @@ fn process_selection_set(
                             &field.selection_set,
                             fragments,
                             var_types,
+                            literals,
                         );
                     }
                 }
@@ -352,11 +444,19 @@ fn process_selection_set(
                         &frag.selection_set,
                         fragments,
                         var_types,
+                        literals,
                     );
                     // Also ensure the fragment's declared type exists.
                     let ty = type_condition_name(&frag.type_condition);
                     draft.ensure_object(&ty);
-                    process_selection_set(draft, &ty, &frag.selection_set, fragments, var_types);
+                    process_selection_set(
+                        draft,
+                        &ty,
+                        &frag.selection_set,
+                        fragments,
+                        var_types,
+                        literals,
+                    );
                 }
             }
             Selection::InlineFragment(inline) => {
@@ -365,7 +465,14 @@ fn process_selection_set(
                     .as_ref()
                     .map(type_condition_name)
                     .unwrap_or_else(|| current_type.to_string());
-                process_selection_set(draft, &ty, &inline.selection_set, fragments, var_types);
+                process_selection_set(
+                    draft,
+                    &ty,
+                    &inline.selection_set,
+                    fragments,
+                    var_types,
+                    literals,
+                );
             }
         }
     }
@@ -376,6 +483,7 @@ fn process_union_selection_set(
     selection_set: &SelectionSet<String>,
     fragments: &HashMap<String, FragmentDefinition<String>>,
     var_types: &HashMap<String, TypeRef>,
+    literals: &mut Vec<InputLiteral>,
 ) {
     for selection in &selection_set.items {
         match selection {
@@ -383,14 +491,28 @@ fn process_union_selection_set(
                 if let Some(tc) = &inline.type_condition {
                     let ty = type_condition_name(tc);
                     draft.ensure_object(&ty);
-                    process_selection_set(draft, &ty, &inline.selection_set, fragments, var_types);
+                    process_selection_set(
+                        draft,
+                        &ty,
+                        &inline.selection_set,
+                        fragments,
+                        var_types,
+                        literals,
+                    );
                 }
             }
             Selection::FragmentSpread(spread) => {
                 if let Some(frag) = fragments.get(&spread.fragment_name) {
                     let ty = type_condition_name(&frag.type_condition);
                     draft.ensure_object(&ty);
-                    process_selection_set(draft, &ty, &frag.selection_set, fragments, var_types);
+                    process_selection_set(
+                        draft,
+                        &ty,
+                        &frag.selection_set,
+                        fragments,
+                        var_types,
+                        literals,
+                    );
                 }
             }
             Selection::Field(_) => {}
@@ -398,6 +520,60 @@ fn process_union_selection_set(
     }
 }
 
+/// An object literal passed as a field argument, e.g. `editTransaction(input: {isReviewed: $r})`.
+struct InputLiteral {
+    object: String,
+    field: String,
+    arg: String,
+    fields: Vec<(String, TypeRef)>,
+}
+
+/// Records the fields of each argument object literal on its input type.
+///
+/// The type comes from a variable passed to the same argument elsewhere
+/// (`$input: EditTransactionInput`); otherwise it's named after the field, e.g.
+/// `editTransaction(input: ...)` -> `EditTransactionInput`.
+fn resolve_input_literals(draft: &mut SchemaDraft, literals: Vec<InputLiteral>) {
+    for lit in literals {
+        let known = draft
+            .objects
+            .get(&lit.object)
+            .and_then(|fields| fields.get(&lit.field))
+            .and_then(|f| f.args.get(&lit.arg))
+            .and_then(|ty| named_type(ty))
+            .filter(|name| draft.inputs.contains(*name))
+            .map(str::to_string);
+        let input_name = match known {
+            Some(name) => name,
+            None => {
+                let name = if lit.arg == "input" {
+                    format!("{}Input", pascal_case(&lit.field))
+                } else {
+                    format!("{}{}Input", pascal_case(&lit.field), pascal_case(&lit.arg))
+                };
+                draft.add_field_arg(
+                    &lit.object,
+                    &lit.field,
+                    &lit.arg,
+                    TypeRef::named(name.clone()),
+                );
+                name
+            }
+        };
+        for (name, ty) in lit.fields {
+            draft.add_input_field(&input_name, &name, ty);
+        }
+    }
+}
+
+fn named_type(ty: &TypeRef) -> Option<&str> {
+    match ty {
+        TypeRef::Named(n) => Some(n),
+        TypeRef::List(_) => None,
+        TypeRef::NonNull(inner) => named_type(inner),
+    }
+}
+
 fn infer_leaf_scalar(field_name: &str) -> TypeRef {
     if field_name == "id" {
         return TypeRef::NonNull(Box::new(TypeRef::named("ID")));
@@ -587,4 +763,69 @@ fragment ThingFields on Thing { id name }
         assert!(out.contains("type EmojiUnicode"));
         assert!(out.contains("unicode"));
     }
+
+    #[test]
+    fn strict_mode_reports_conflicting_field_types() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().join("a.graphql");
+        fs::write(
+            &p,
+            r#"
+query A { node { ...FooFields } }
+query B { node { ...BarFields } }
+fragment FooFields on Foo { id }
+fragment BarFields on Bar { id }
+"#,
+        )
+        .unwrap();
+
+        let lenient = render_schema_from_operations(std::slice::from_ref(&p)).unwrap();
+        assert!(lenient.contains("  node: JSON\n"));
+
+        let err = render_schema_from_operations_with(&[p], true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ambiguous types:\nfield Query.node has conflicting types: Foo vs Bar"
+        );
+    }
+
+    #[test]
+    fn strict_mode_does_not_flag_fields_with_arguments() {
+        let tmp = tempfile::tempdir().unwrap();
+        let p = tmp.path().join("a.graphql");
+        fs::write(
+            &p,
+            r#"query Q($first: Int) { things(first: $first) { ...ThingFields } }
+fragment ThingFields on Thing { id }"#,
+        )
+        .unwrap();
+
+        let out = render_schema_from_operations_with(&[p], true).unwrap();
+        assert!(out.contains("  things(first: Int): Thing\n"));
+    }
+
+    #[test]
+    fn schema_infers_input_fields_from_argument_literals() {
+        let tmp = tempfile::tempdir().unwrap();
+        let typed = tmp.path().join("typed.graphql");
+        std::fs::write(
+            &typed,
+            r#"mutation EditTransaction($id: ID!, $input: EditTransactionInput) { editTransaction(id: $id, input: $input) { id } }"#,
+        )
+        .unwrap();
+        let literal = tmp.path().join("literal.graphql");
+        std::fs::write(
+            &literal,
+            r#"mutation Review($id: ID!, $isReviewed: Boolean, $categoryId: ID, $userNotes: String) {
+  editTransaction(id: $id, input: {isReviewed: $isReviewed, categoryId: $categoryId, userNotes: $userNotes}) { id }
+}"#,
+        )
+        .unwrap();
+
+        let out = crate::schema_gen::render_schema_from_operations(&[typed, literal]).unwrap();
+        assert!(out.contains(
+            "input EditTransactionInput {\n  categoryId: ID\n  isReviewed: Boolean\n  userNotes: String\n}"
+        ));
+        assert!(out.contains("editTransaction(id: ID!, input: EditTransactionInput)"));
+    }
 }
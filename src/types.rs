@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
@@ -57,6 +58,20 @@ impl<T> Hash for OwnedId<T> {
     }
 }
 
+// `Hash`/`Eq` delegate to the raw string, so these are consistent with `str`/`String` and
+// let maps keyed by an id be queried with a plain `&str` (`map.get("txn_1")`).
+impl<T> Borrow<str> for OwnedId<T> {
+    fn borrow(&self) -> &str {
+        self.raw.as_str()
+    }
+}
+
+impl<T> Borrow<String> for OwnedId<T> {
+    fn borrow(&self) -> &String {
+        &self.raw
+    }
+}
+
 impl<T> FromStr for OwnedId<T> {
     type Err = std::convert::Infallible;
 
@@ -151,3 +166,21 @@ impl fmt::Display for RecurringFrequency {
         write!(f, "{s}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::TransactionId;
+
+    #[test]
+    fn id_keyed_map_can_be_queried_with_str_and_string() {
+        let mut map: HashMap<TransactionId, u32> = HashMap::new();
+        map.insert(TransactionId::from("txn_1"), 1);
+
+        assert_eq!(map.get("txn_1"), Some(&1));
+        let key = String::from("txn_1");
+        assert_eq!(map.get::<String>(&key), Some(&1));
+        assert_eq!(map.get("txn_2"), None);
+    }
+}
@@ -115,32 +115,111 @@ fn mutations_require_yes_or_dry_run() {
 }
 
 #[test]
-fn tags_list_and_create_work() {
+fn tags_create_if_not_exists_returns_existing_tag() {
     let tmp_home = tempfile::tempdir().unwrap();
 
     cmd_with_fixtures(&tmp_home)
-        .args(["tags", "list"])
+        .args(["tags", "create", "--if-not-exists", "shopping"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("Shopping"));
+        .stdout(predicate::str::contains("tag_shopping"))
+        .stdout(predicate::str::contains("tag_new").not())
+        .stderr(predicate::str::contains("already exists"));
 
     cmd_with_fixtures(&tmp_home)
-        .args(["--dry-run", "tags", "create", "New Tag"])
+        .args(["--yes", "tags", "create", "--if-not-exists", "New Tag"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("dry-run: would create tag"));
+        .stdout(predicate::str::contains("tag_new"));
+}
 
+#[test]
+fn recurrings_create_batch_validates_csv_and_reports_failures() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let bad = tmp_home.path().join("bad.csv");
+    std::fs::write(&bad, "transaction_id,frequency,name\ntxn_1,fortnightly,\n").unwrap();
     cmd_with_fixtures(&tmp_home)
-        .args(["tags", "create", "New Tag"])
+        .args(["--yes", "recurrings", "create-batch"])
+        .arg(&bad)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "line 2: unknown frequency \"fortnightly\"",
+        ));
+
+    let csv = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/recurrings_batch.csv");
+    cmd_with_fixtures(&tmp_home)
+        .args(["recurrings", "create-batch"])
+        .arg(&csv)
         .assert()
         .failure()
         .stderr(predicate::str::contains("--yes"));
 
     cmd_with_fixtures(&tmp_home)
-        .args(["--yes", "tags", "create", "New Tag"])
+        .args(["--yes", "recurrings", "create-batch"])
+        .arg(&csv)
         .assert()
-        .success()
-        .stdout(predicate::str::contains("tag_new"));
+        .failure()
+        .stdout(predicate::str::contains("transaction not found"))
+        .stderr(predicate::str::contains("failed to create 1 recurring(s)"));
+}
+
+#[test]
+fn budgets_set_from_csv_validates_rows() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let cases = [
+        (
+            "category_id,amount\ncat_other,lots\n",
+            "line 2: invalid amount \"lots\"",
+        ),
+        (
+            "category_id,category_name,amount\ncat_other,Other,10\n",
+            "line 2: expected exactly one of category_id or category_name",
+        ),
+        (
+            "category_name,amount\nOther,10\nNope,5\n",
+            "line 3: no category named \"Nope\"",
+        ),
+        (
+            "category_id,amount\ncat_missing,10\n",
+            "line 2: unknown category id cat_missing",
+        ),
+    ];
+    for (i, (csv, expected)) in cases.into_iter().enumerate() {
+        let path = tmp_home.path().join(format!("budgets_{i}.csv"));
+        std::fs::write(&path, csv).unwrap();
+        cmd_with_fixtures(&tmp_home)
+            .args(["--dry-run", "budgets", "set-from-csv"])
+            .arg(&path)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(expected));
+    }
+
+    let csv = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/budgets.csv");
+    cmd_with_fixtures(&tmp_home)
+        .args(["--dry-run", "budgets", "set-from-csv", "--month", "2026-13"])
+        .arg(&csv)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid --month \"2026-13\""));
+    cmd_with_fixtures(&tmp_home)
+        .args(["--yes", "budgets", "set-from-csv"])
+        .arg(&csv)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not implemented yet"));
+}
+
+#[test]
+fn budgets_rollover_is_not_implemented_without_dry_run() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["--yes", "budgets", "rollover", "cat_other", "--enable"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not implemented yet"));
 }
 
 #[test]
@@ -166,3 +245,853 @@ fn tags_delete_requires_yes_or_dry_run() {
         .stdout(predicate::str::contains("\"deleted\""))
         .stdout(predicate::str::contains("\"true\""));
 }
+
+#[test]
+fn global_date_range_filters_budget_months() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args([
+            "--output",
+            "json",
+            "--global-date-from",
+            "2025-12-01",
+            "budgets",
+            "month",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2025-12"))
+        .stdout(predicate::str::contains("2025-11").not());
+}
+
+#[test]
+fn global_date_range_warns_when_ignored() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["--global-date-from", "2025-12-01", "tags", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("ignored by this command"));
+}
+
+#[test]
+fn invalid_date_range_is_rejected() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["transactions", "list", "--date-from", "2025/12/01"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid --date-from"));
+}
+
+#[test]
+fn watch_run_once_prints_transactions_and_exits() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let state = tmp_home.path().join("watch-state.json");
+    std::fs::write(&state, r#"{"date": "2025-01-01", "ids": []}"#).unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["watch", "--run-once", "--fields", "date,name,amount"])
+        .arg("--state-file")
+        .arg(&state)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Venmo"))
+        .stdout(predicate::str::contains("Amazon.com"));
+}
+
+#[test]
+fn watch_run_once_first_run_only_records_marker() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["watch", "--run-once"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let state = tmp_home
+        .path()
+        .join(".config/copilot-money-cli/watch-state.json");
+    let saved: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(state).unwrap()).unwrap();
+    assert!(saved["date"].as_str().is_some_and(|d| !d.is_empty()));
+}
+
+#[test]
+fn watch_rejects_zero_interval() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["watch", "--interval", "0"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn transactions_list_output_file_matches_stdout() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let expected = cmd_with_fixtures(&tmp_home)
+        .args(["--output", "json", "transactions", "list"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let path = tmp_home.path().join("export.json");
+    cmd_with_fixtures(&tmp_home)
+        .args(["--output", "json", "transactions", "list", "--output-file"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+}
+
+fn stub_token_helper(dir: &std::path::Path) -> std::path::PathBuf {
+    let helper = dir.join("get_token.py");
+    std::fs::write(
+        &helper,
+        "import os, sys\n\
+         open(os.path.join(os.path.dirname(__file__), 'args.txt'), 'w').write(' '.join(sys.argv[1:]))\n\
+         open(os.path.join(os.path.dirname(__file__), 'env.txt'), 'w').write(os.environ.get('COPILOT_LOGIN_PASSWORD', ''))\n\
+         print('stub_token')\n",
+    )
+    .unwrap();
+    helper
+}
+
+#[test]
+fn auth_login_email_link_passes_email_to_helper() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let helper = stub_token_helper(tmp_home.path());
+    let token_file = tmp_home.path().join("token");
+    cmd_with_fixtures(&tmp_home)
+        .env("COPILOT_TOKEN_HELPER", &helper)
+        .arg("--token-file")
+        .arg(&token_file)
+        .args([
+            "auth",
+            "login",
+            "--mode",
+            "email-link",
+            "--email",
+            "you@example.com",
+            "--no-persist-session",
+        ])
+        .assert()
+        .success();
+
+    let args = std::fs::read_to_string(tmp_home.path().join("args.txt")).unwrap();
+    assert!(args.contains("--mode email-link"));
+    assert!(args.contains("--email you@example.com"));
+    assert_eq!(
+        std::fs::read_to_string(&token_file).unwrap().trim(),
+        "stub_token"
+    );
+}
+
+#[test]
+fn auth_login_no_save_prints_token_without_writing_file() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let helper = stub_token_helper(tmp_home.path());
+    let token_file = tmp_home.path().join("token");
+    cmd_with_fixtures(&tmp_home)
+        .env("COPILOT_TOKEN_HELPER", &helper)
+        .arg("--token-file")
+        .arg(&token_file)
+        .args(["auth", "login", "--no-save", "--no-persist-session"])
+        .assert()
+        .success()
+        .stdout("stub_token\n");
+
+    assert!(!token_file.exists());
+}
+
+#[test]
+fn auth_login_passes_timeout_seconds_to_helper() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let helper = stub_token_helper(tmp_home.path());
+    cmd_with_fixtures(&tmp_home)
+        .env("COPILOT_TOKEN_HELPER", &helper)
+        .arg("--token-file")
+        .arg(tmp_home.path().join("token"))
+        .args([
+            "auth",
+            "login",
+            "--timeout-seconds",
+            "42",
+            "--no-persist-session",
+        ])
+        .assert()
+        .success();
+
+    let args = std::fs::read_to_string(tmp_home.path().join("args.txt")).unwrap();
+    assert!(args.contains("--timeout-seconds 42"));
+    assert!(args.contains("--mode interactive"));
+}
+
+#[test]
+fn auth_login_rejects_zero_timeout_seconds() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["auth", "login", "--timeout-seconds", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--timeout-seconds"));
+}
+
+#[test]
+fn auth_login_email_link_prompts_for_missing_email() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let helper = stub_token_helper(tmp_home.path());
+    cmd_with_fixtures(&tmp_home)
+        .env("COPILOT_TOKEN_HELPER", &helper)
+        .arg("--token-file")
+        .arg(tmp_home.path().join("token"))
+        .args([
+            "auth",
+            "login",
+            "--mode",
+            "email-link",
+            "--no-persist-session",
+        ])
+        .write_stdin("prompted@example.com\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Email: "));
+
+    let args = std::fs::read_to_string(tmp_home.path().join("args.txt")).unwrap();
+    assert!(args.contains("--email prompted@example.com"));
+}
+
+#[test]
+fn transactions_list_limit_zero_is_treated_as_all() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let all = cmd_with_fixtures(&tmp_home)
+        .args(["--output", "json", "transactions", "list", "--all"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    cmd_with_fixtures(&tmp_home)
+        .args(["--output", "json", "transactions", "list", "--limit", "0"])
+        .assert()
+        .success()
+        .stdout(all)
+        .stderr(predicate::str::contains("--limit 0 is treated as --all"));
+}
+
+#[test]
+fn batch_runs_each_line_and_summarizes() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let batch = tmp_home.path().join("batch.jsonl");
+    std::fs::write(
+        &batch,
+        r#"{"command": ["transactions", "review", "txn_1"]}
+
+{"command": ["transactions", "set-notes", "txn_1", "--notes", "paid"]}
+"#,
+    )
+    .unwrap();
+
+    cmd_with_fixtures(&tmp_home)
+        .args(["--yes", "batch"])
+        .arg(&batch)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("batch: 2 succeeded, 0 failed"));
+}
+
+#[test]
+fn batch_honors_per_line_globals() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let batch = tmp_home.path().join("batch.jsonl");
+    let line_out = tmp_home.path().join("tags.json");
+    std::fs::write(
+        &batch,
+        format!(
+            r#"{{"command": ["transactions", "review", "txn_1", "--dry-run"]}}
+{{"command": ["--dry-run", "transactions", "set-notes", "txn_1", "--notes", "paid"]}}
+{{"command": ["--output", "json", "--output-file", "{}", "tags", "list"]}}
+"#,
+            line_out.display()
+        ),
+    )
+    .unwrap();
+
+    // Only the dry-run messages reach stdout: neither write ran, and the tags went to the file.
+    let out = cmd_with_fixtures(&tmp_home)
+        .args(["--yes", "batch"])
+        .arg(&batch)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.lines().count(), 2);
+    assert!(out.lines().all(|l| l.starts_with("dry-run: would")));
+    let tags: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&line_out).unwrap()).unwrap();
+    assert!(tags.is_array());
+
+    std::fs::write(
+        &batch,
+        r#"{"command": ["--base-url", "http://example.invalid", "tags", "list"]}
+"#,
+    )
+    .unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["--yes", "batch"])
+        .arg(&batch)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--base-url can't be set per batch line",
+        ));
+}
+
+#[test]
+fn batch_continues_after_errors_unless_fail_fast() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let batch = tmp_home.path().join("batch.jsonl");
+    std::fs::write(
+        &batch,
+        r#"{"command": ["transactions", "bogus"]}
+{"command": ["tags", "list"]}
+"#,
+    )
+    .unwrap();
+
+    cmd_with_fixtures(&tmp_home)
+        .args(["--yes", "batch"])
+        .arg(&batch)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("batch line 1:"))
+        .stderr(predicate::str::contains("batch: 1 succeeded, 1 failed"));
+
+    cmd_with_fixtures(&tmp_home)
+        .args(["--yes", "batch", "--fail-fast"])
+        .arg(&batch)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("batch: 0 succeeded, 1 failed"));
+}
+
+#[test]
+fn batch_requires_yes() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let batch = tmp_home.path().join("batch.jsonl");
+    std::fs::write(&batch, "").unwrap();
+
+    cmd_with_fixtures(&tmp_home)
+        .arg("batch")
+        .arg(&batch)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--yes"));
+}
+
+#[test]
+fn append_output_file_accumulates_runs() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let path = tmp_home.path().join("log.json");
+    for _ in 0..2 {
+        cmd_with_fixtures(&tmp_home)
+            .args(["--output", "json", "--append-output-file"])
+            .arg(&path)
+            .args(["accounts", "show", "acct_1"])
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+    }
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    let records = serde_json::Deserializer::from_str(&written)
+        .into_iter::<serde_json::Value>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0], records[1]);
+}
+
+#[test]
+fn global_output_file_captures_output_for_any_command() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let path = tmp_home.path().join("tags.json");
+    cmd_with_fixtures(&tmp_home)
+        .args(["--output", "json", "--output-file"])
+        .arg(&path)
+        .args(["tags", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written[0]["id"], "tag_gifts");
+
+    cmd_with_fixtures(&tmp_home)
+        .args(["--output-file", "-", "tags", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tag_shopping"));
+}
+
+#[test]
+fn transactions_list_page_size_and_limit_alias_agree() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let by_limit = cmd_with_fixtures(&tmp_home)
+        .args(["--output", "json", "transactions", "list", "--limit", "1"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    cmd_with_fixtures(&tmp_home)
+        .args([
+            "--output",
+            "json",
+            "transactions",
+            "list",
+            "--page-size",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout(by_limit);
+
+    cmd_with_fixtures(&tmp_home)
+        .args(["transactions", "list", "--page-size", "1", "--limit", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used multiple times"));
+}
+
+#[test]
+fn auth_login_credentials_reads_json_secrets_file() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let helper = stub_token_helper(tmp_home.path());
+    let secrets = tmp_home.path().join("secrets.json");
+    std::fs::write(
+        &secrets,
+        r#"{"email": "me@example.com", "password": "hunter2"}"#,
+    )
+    .unwrap();
+
+    cmd_with_fixtures(&tmp_home)
+        .env("COPILOT_TOKEN_HELPER", &helper)
+        .arg("--token-file")
+        .arg(tmp_home.path().join("token"))
+        .args([
+            "auth",
+            "login",
+            "--mode",
+            "credentials",
+            "--no-persist-session",
+        ])
+        .arg("--secrets-file")
+        .arg(&secrets)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("warning: --mode credentials"))
+        .stderr(predicate::str::contains("hunter2").not());
+
+    let args = std::fs::read_to_string(tmp_home.path().join("args.txt")).unwrap();
+    assert!(args.contains("--mode credentials --email me@example.com"));
+    assert!(!args.contains("hunter2"));
+    let env = std::fs::read_to_string(tmp_home.path().join("env.txt")).unwrap();
+    assert_eq!(env, "hunter2");
+}
+
+#[test]
+fn auth_login_credentials_rejects_malformed_secrets_file() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let helper = stub_token_helper(tmp_home.path());
+    let secrets = tmp_home.path().join("secrets.json");
+    std::fs::write(&secrets, r#"{"email": "me@example.com"}"#).unwrap();
+
+    cmd_with_fixtures(&tmp_home)
+        .env("COPILOT_TOKEN_HELPER", &helper)
+        .args([
+            "auth",
+            "login",
+            "--mode",
+            "credentials",
+            "--no-persist-session",
+        ])
+        .arg("--secrets-file")
+        .arg(&secrets)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("malformed secrets file"));
+}
+
+#[test]
+fn set_category_all_matching_requires_yes() {
+    let tmp_home = tempfile::tempdir().unwrap();
+
+    cmd_with_fixtures(&tmp_home)
+        .args([
+            "transactions",
+            "set-category",
+            "--category",
+            "Shops",
+            "--all-matching",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --yes"));
+
+    cmd_with_fixtures(&tmp_home)
+        .args([
+            "transactions",
+            "set-category",
+            "--category",
+            "Shops",
+            "--unreviewed",
+            "txn_1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--all-matching"));
+}
+
+#[test]
+fn format_errors_json_reports_missing_transaction() {
+    let tmp_home = tempfile::tempdir().unwrap();
+
+    let out = cmd_with_fixtures(&tmp_home)
+        .args([
+            "--format-errors",
+            "json",
+            "transactions",
+            "show",
+            "txn_missing",
+        ])
+        .assert()
+        .code(1)
+        .stdout("")
+        .get_output()
+        .stderr
+        .clone();
+
+    let err: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(err["kind"], "not-found");
+    assert!(err["error"].as_str().unwrap().contains("not found"));
+}
+
+#[test]
+fn bulk_review_rejects_list_only_flags() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    for flag in [["--pages", "2"], ["--fields", "name"]] {
+        cmd_with_fixtures(&tmp_home)
+            .args(["--dry-run", "transactions", "bulk-review"])
+            .args(flag)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unexpected argument"));
+    }
+}
+
+#[test]
+fn bulk_review_partial_failure_shows_both_sections_and_fails() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let fixtures = tmp_home.path().join("fixtures");
+    std::fs::create_dir(&fixtures).unwrap();
+    for entry in std::fs::read_dir("tests/fixtures/graphql").unwrap() {
+        let path = entry.unwrap().path();
+        std::fs::copy(&path, fixtures.join(path.file_name().unwrap())).unwrap();
+    }
+    std::fs::copy(
+        "tests/fixtures/graphql_partial_failure/BulkEditTransactions.json",
+        fixtures.join("BulkEditTransactions.json"),
+    )
+    .unwrap();
+
+    cmd_with_fixtures(&tmp_home)
+        .env("COPILOT_FIXTURES_DIR", &fixtures)
+        .args(["--yes", "transactions", "review", "txn_1", "txn_2"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("ok"))
+        .stdout(predicate::str::contains("txn_1"))
+        .stdout(predicate::str::contains("failed"))
+        .stdout(predicate::str::contains("TRANSACTION_LOCKED"))
+        .stdout(predicate::str::contains("Transaction is locked"))
+        .stderr(predicate::str::contains(
+            "bulk edit failed for 1 transaction(s)",
+        ));
+}
+
+#[test]
+fn config_set_and_get_update_individual_keys() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let dir = tmp_home.path().join(".config/copilot-money-cli");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("config.toml"), "# my defaults\ncolor = \"auto\"\n").unwrap();
+
+    for (key, value) in [
+        ("output", "json"),
+        ("color", "never"),
+        ("transactions.list.limit", "50"),
+    ] {
+        cmd_with_fixtures(&tmp_home)
+            .args(["config", "set", key, value])
+            .assert()
+            .success();
+        cmd_with_fixtures(&tmp_home)
+            .args(["config", "get", key])
+            .assert()
+            .success()
+            .stdout(format!("{value}\n"));
+    }
+
+    let written = std::fs::read_to_string(dir.join("config.toml")).unwrap();
+    assert!(written.starts_with("# my defaults\n"));
+    assert!(written.contains("[transactions.list]\nlimit = 50"));
+
+    // The stored `output` becomes the default, and an explicit flag still wins.
+    cmd_with_fixtures(&tmp_home)
+        .args(["tags", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("["));
+    cmd_with_fixtures(&tmp_home)
+        .args(["--output", "table", "tags", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("color_name"));
+}
+
+#[test]
+fn config_set_rejects_invalid_values_and_unknown_keys() {
+    let tmp_home = tempfile::tempdir().unwrap();
+
+    for (key, value, msg) in [
+        ("output", "yaml", "unknown variant `yaml`"),
+        ("transactions.list.limit", "lots", "expected usize"),
+        ("nope", "1", "unknown field `nope`"),
+    ] {
+        cmd_with_fixtures(&tmp_home)
+            .args(["config", "set", key, value])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(msg));
+    }
+    assert!(
+        !tmp_home
+            .path()
+            .join(".config/copilot-money-cli/config.toml")
+            .exists()
+    );
+
+    cmd_with_fixtures(&tmp_home)
+        .args(["config", "get", "output"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("output is not set"));
+}
+
+#[test]
+fn config_json_schema_describes_config_keys() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let out = cmd_with_fixtures(&tmp_home)
+        .args(["config", "json-schema"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let schema: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(schema["title"], "Config");
+    assert!(schema["properties"]["output"].is_object());
+    assert_eq!(
+        schema["$defs"]["OutputFormat"]["enum"],
+        serde_json::json!(["json", "table"])
+    );
+}
+
+#[test]
+fn categories_export_csv_round_trips() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let path = tmp_home.path().join("categories.csv");
+    cmd_with_fixtures(&tmp_home)
+        .args(["categories", "export", "--spend", "--budget"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("exported 4 categories"));
+
+    let mut reader = csv::Reader::from_path(&path).unwrap();
+    assert_eq!(
+        reader.headers().unwrap(),
+        vec![
+            "id",
+            "name",
+            "parent_id",
+            "is_excluded",
+            "can_be_deleted",
+            "color_name",
+            "template_id",
+            "current_spend",
+            "budget_amount",
+            "rollover_disabled",
+        ]
+    );
+    let rows = reader
+        .records()
+        .map(|r| r.unwrap())
+        .collect::<Vec<csv::StringRecord>>();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(&rows[1][0], "cat_shops");
+    assert_eq!(&rows[1][1], "Shops");
+    assert_eq!(&rows[1][7], "$257.48");
+    assert_eq!(&rows[2][0], "cat_shops_clothing");
+    assert_eq!(&rows[2][2], "cat_shops");
+    assert_eq!(&rows[3][3], "true");
+
+    let out = cmd_with_fixtures(&tmp_home)
+        .args(["categories", "export", "-", "--name-contains", "shop"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let mut reader = csv::Reader::from_reader(out.as_slice());
+    assert_eq!(reader.headers().unwrap().len(), 7);
+    assert_eq!(reader.records().count(), 1);
+}
+
+#[test]
+fn completions_zsh_includes_subcommands_and_value_enums() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["completions", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("transactions"))
+        // `--fields` values and `--frequency` values.
+        .stdout(predicate::str::contains("account-id"))
+        .stdout(predicate::str::contains("monthly"));
+}
+
+#[test]
+fn categories_list_sort_spend_requires_spend() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["categories", "list", "--sort", "spend"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--sort spend requires --spend"));
+}
+
+#[test]
+fn transactions_list_rejects_unknown_field() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["transactions", "list", "--fields", "date,merchant"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown field 'merchant'; valid fields are: date,name,amount,",
+        ));
+}
+
+#[test]
+fn transactions_list_since_cursor_rejects_api_cursors() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["transactions", "list", "--since-cursor", "c0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid --since-cursor \"c0\""));
+}
+
+#[test]
+fn transactions_list_amount_range_conflicts_with_amount_sign() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    for args in [
+        ["--amount-positive", "--amount-max", "-5"],
+        ["--amount-negative", "--amount-min", "10"],
+    ] {
+        cmd_with_fixtures(&tmp_home)
+            .args(["transactions", "list"])
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+#[test]
+fn recurrings_edit_rule_json_must_be_an_object() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args([
+            "--dry-run",
+            "recurrings",
+            "edit",
+            "rec_1",
+            "--rule-json",
+            "[1]",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--rule-json must be a JSON object",
+        ));
+}
+
+#[test]
+fn version_includes_git_hash_and_build_date() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .arg("version")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"^copilot-money-cli \d+\.\d+\.\d+ \(([0-9a-f]{7,}|unknown), (\d{4}-\d{2}-\d{2}|unknown)\)\n$").unwrap());
+}
+
+#[test]
+fn version_json_has_build_fields() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let out = cmd_with_fixtures(&tmp_home)
+        .args(["--output", "json", "version"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["version"], env!("CARGO_PKG_VERSION"));
+    assert!(v["git_hash"].as_str().is_some_and(|s| !s.is_empty()));
+    assert!(v["build_date"].as_str().is_some_and(|s| !s.is_empty()));
+}
+
+#[test]
+fn tags_list_sort_count_requires_with_counts() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["tags", "list", "--sort", "count"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--sort count requires --with-counts",
+        ));
+}
+
+#[test]
+fn categories_merge_requires_yes() {
+    let tmp_home = tempfile::tempdir().unwrap();
+    cmd_with_fixtures(&tmp_home)
+        .args(["categories", "merge", "cat_shops", "cat_other"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--yes"));
+}
@@ -1,17 +1,34 @@
 use assert_cmd::Command;
 
 fn run(args: &[&str]) -> String {
+    run_with_fixtures("tests/fixtures/graphql", args)
+}
+
+fn run_with_fixtures(fixtures_dir: &str, args: &[&str]) -> String {
     let tmp_home = tempfile::tempdir().unwrap();
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("copilot"));
     cmd.env("HOME", tmp_home.path());
     cmd.env_remove("COPILOT_TOKEN");
     cmd.env_remove("COPILOT_TOKEN_FILE");
-    cmd.env("COPILOT_FIXTURES_DIR", "tests/fixtures/graphql");
+    cmd.env("COPILOT_FIXTURES_DIR", fixtures_dir);
     cmd.args(args);
     let out = cmd.assert().success().get_output().stdout.clone();
     String::from_utf8(out).unwrap()
 }
 
+/// Like `run`, for commands that print their output and then exit non-zero (partial failures).
+fn run_failing(args: &[&str]) -> String {
+    let tmp_home = tempfile::tempdir().unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("copilot"));
+    cmd.env("HOME", tmp_home.path());
+    cmd.env_remove("COPILOT_TOKEN");
+    cmd.env_remove("COPILOT_TOKEN_FILE");
+    cmd.env("COPILOT_FIXTURES_DIR", "tests/fixtures/graphql");
+    cmd.args(args);
+    let out = cmd.assert().failure().get_output().stdout.clone();
+    String::from_utf8(out).unwrap()
+}
+
 #[test]
 fn transactions_list_table_snapshot() {
     insta::assert_snapshot!(run(&["transactions", "list"]));
@@ -27,6 +44,17 @@ fn transactions_list_table_page_info_snapshot() {
     insta::assert_snapshot!(run(&["transactions", "list", "--page-info"]));
 }
 
+#[test]
+fn transactions_list_skip_pages_past_end_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "transactions",
+        "list",
+        "--skip-pages",
+        "1",
+        "--page-info"
+    ]));
+}
+
 #[test]
 fn transactions_list_json_page_info_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -43,6 +71,11 @@ fn transactions_list_table_filter_tag_snapshot() {
     insta::assert_snapshot!(run(&["transactions", "list", "--tag", "Shopping"]));
 }
 
+#[test]
+fn transactions_list_table_filter_tag_id_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "list", "--tag-id", "tag_shopping"]));
+}
+
 #[test]
 fn transactions_list_table_filter_category_snapshot() {
     insta::assert_snapshot!(run(&["transactions", "list", "--category-id", "cat_other"]));
@@ -74,6 +107,11 @@ fn transactions_show_json_snapshot() {
     insta::assert_snapshot!(run(&["--output", "json", "transactions", "show", "txn_1"]));
 }
 
+#[test]
+fn transactions_show_all_fields_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "show", "txn_2"]));
+}
+
 #[test]
 fn transactions_list_table_fields_and_sort_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -86,6 +124,11 @@ fn transactions_list_table_fields_and_sort_snapshot() {
     ]));
 }
 
+#[test]
+fn transactions_list_fields_all_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "list", "--fields", "all"]));
+}
+
 #[test]
 fn transactions_list_table_filter_reviewed_snapshot() {
     insta::assert_snapshot!(run(&["transactions", "list", "--reviewed"]));
@@ -101,6 +144,127 @@ fn transactions_list_table_filter_date_snapshot() {
     insta::assert_snapshot!(run(&["transactions", "list", "--date", "12-15-2025"]));
 }
 
+#[test]
+fn transactions_list_table_filter_merchant_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "list", "--merchant", "amazon"]));
+}
+
+#[test]
+fn transactions_list_has_recurring_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "list", "--has-recurring"]));
+}
+
+#[test]
+fn transactions_list_no_recurring_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "list", "--no-recurring"]));
+}
+
+#[test]
+fn transactions_list_amount_positive_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_mixed_amounts",
+        &["transactions", "list", "--amount-positive"]
+    ));
+}
+
+#[test]
+fn transactions_list_amount_negative_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_mixed_amounts",
+        &["transactions", "list", "--amount-negative"]
+    ));
+}
+
+#[test]
+fn transactions_list_amount_range_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_mixed_amounts",
+        &[
+            "transactions",
+            "list",
+            "--amount-min",
+            "-100",
+            "--amount-max",
+            "0"
+        ]
+    ));
+}
+
+#[test]
+fn transactions_list_exclude_type_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_mixed_amounts",
+        &["transactions", "list", "--exclude-type", "regular"]
+    ));
+}
+
+#[test]
+fn transactions_list_hide_internal_transfers_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_mixed_amounts",
+        &["transactions", "list", "--hide-internal-transfers"]
+    ));
+}
+
+#[test]
+fn transactions_list_client_sort_name_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "list", "--client-sort", "name"]));
+}
+
+#[test]
+fn transactions_list_client_sort_amount_snapshot() {
+    insta::assert_snapshot!(run(&["transactions", "list", "--client-sort", "amount"]));
+}
+
+#[test]
+fn transactions_bulk_review_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "transactions",
+        "bulk-review",
+        "--unreviewed",
+        "--category",
+        "Other",
+    ]));
+}
+
+#[test]
+fn transactions_bulk_review_table_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--yes",
+        "transactions",
+        "bulk-review",
+        "--unreviewed",
+    ]));
+}
+
+#[test]
+fn transactions_set_category_all_matching_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "transactions",
+        "set-category",
+        "--category",
+        "Shops",
+        "--all-matching",
+        "--name-contains",
+        "venmo",
+    ]));
+}
+
+#[test]
+fn transactions_set_category_all_matching_table_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--yes",
+        "transactions",
+        "set-category",
+        "--category",
+        "Shops",
+        "--all-matching",
+        "--unreviewed",
+    ]));
+}
+
 #[test]
 fn transactions_set_category_by_name_table_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -125,6 +289,32 @@ fn transactions_set_notes_table_snapshot() {
     ]));
 }
 
+#[test]
+fn transactions_append_notes_table_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--yes",
+        "transactions",
+        "set-notes",
+        "txn_1",
+        "--notes",
+        "split with Sam",
+        "--append",
+    ]));
+}
+
+#[test]
+fn transactions_prepend_notes_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "transactions",
+        "set-notes",
+        "txn_1",
+        "--notes",
+        "TODO:",
+        "--prepend",
+    ]));
+}
+
 #[test]
 fn transactions_clear_notes_table_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -150,6 +340,18 @@ fn transactions_set_tags_add_table_snapshot() {
     ]));
 }
 
+#[test]
+fn transactions_set_tags_add_name_table_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--yes",
+        "transactions",
+        "set-tags",
+        "txn_1",
+        "--add-name",
+        "Shopping",
+    ]));
+}
+
 #[test]
 fn transactions_assign_recurring_table_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -178,6 +380,11 @@ fn auth_status_table_snapshot() {
     insta::assert_snapshot!(run(&["auth", "status"]));
 }
 
+#[test]
+fn auth_status_with_user_info_snapshot() {
+    insta::assert_snapshot!(run(&["--token", "test-token", "auth", "status"]));
+}
+
 #[test]
 fn auth_status_json_snapshot() {
     insta::assert_snapshot!(run(&["--output", "json", "auth", "status"]));
@@ -188,11 +395,52 @@ fn categories_list_table_snapshot() {
     insta::assert_snapshot!(run(&["categories", "list"]));
 }
 
+#[test]
+fn categories_list_count_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--count"]));
+}
+
+#[test]
+fn categories_list_count_children_quiet_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "categories",
+        "list",
+        "--count",
+        "--children",
+        "--quiet"
+    ]));
+}
+
+#[test]
+fn categories_list_count_json_snapshot() {
+    insta::assert_snapshot!(run(&["--output", "json", "categories", "list", "--count"]));
+}
+
+#[test]
+fn categories_list_excluded_only_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--excluded-only"]));
+}
+
+#[test]
+fn categories_list_included_only_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--included-only"]));
+}
+
+#[test]
+fn categories_list_excluded_false_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--excluded", "false"]));
+}
+
 #[test]
 fn categories_list_json_snapshot() {
     insta::assert_snapshot!(run(&["--output", "json", "categories", "list"]));
 }
 
+#[test]
+fn categories_list_with_icons_table_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--with-icons"]));
+}
+
 #[test]
 fn recurrings_list_table_snapshot() {
     insta::assert_snapshot!(run(&["recurrings", "list"]));
@@ -203,11 +451,126 @@ fn recurrings_list_json_snapshot() {
     insta::assert_snapshot!(run(&["--output", "json", "recurrings", "list"]));
 }
 
+#[test]
+fn categories_list_budget_table_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--budget"]));
+}
+
+#[test]
+fn categories_list_spend_total_table_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--spend", "--total"]));
+}
+
+#[test]
+fn categories_list_spend_total_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "categories",
+        "list",
+        "--spend",
+        "--total",
+    ]));
+}
+
+#[test]
+fn categories_list_spend_budget_status_table_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_mixed_amounts",
+        &["categories", "list", "--spend", "--budget"]
+    ));
+}
+
+#[test]
+fn categories_list_spend_budget_status_json_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_mixed_amounts",
+        &[
+            "--output",
+            "json",
+            "categories",
+            "list",
+            "--spend",
+            "--budget"
+        ]
+    ));
+}
+
+#[test]
+fn categories_list_spend_percentage_table_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "categories",
+        "list",
+        "--spend",
+        "--with-spend-percentage",
+        "--total",
+    ]));
+}
+
+#[test]
+fn categories_list_spend_percentage_filtered_snapshot() {
+    // Shares and the total stay relative to all spend, not just the rows left by the filter.
+    insta::assert_snapshot!(run(&[
+        "categories",
+        "list",
+        "--spend",
+        "--with-spend-percentage",
+        "--total",
+        "--name-contains",
+        "shop",
+    ]));
+}
+
+#[test]
+fn categories_list_spend_percentage_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "categories",
+        "list",
+        "--spend",
+        "--with-spend-percentage",
+    ]));
+}
+
+#[test]
+fn categories_list_sort_spend_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--spend", "--sort", "spend"]));
+}
+
+#[test]
+fn categories_list_sort_name_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--sort", "name"]));
+}
+
 #[test]
 fn categories_show_table_snapshot() {
     insta::assert_snapshot!(run(&["categories", "show", "cat_other"]));
 }
 
+#[test]
+fn categories_show_spend_table_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "show", "cat_other", "--spend"]));
+}
+
+#[test]
+fn categories_show_budget_table_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "show", "cat_other", "--budget"]));
+}
+
+#[test]
+fn categories_show_spend_budget_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "categories",
+        "show",
+        "cat_other",
+        "--spend",
+        "--budget",
+    ]));
+}
+
 #[test]
 fn categories_show_json_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -219,6 +582,59 @@ fn categories_show_json_snapshot() {
     ]));
 }
 
+#[test]
+fn categories_create_subcategory_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--yes",
+        "categories",
+        "create",
+        "New Category",
+        "--parent",
+        "Other",
+    ]));
+}
+
+#[test]
+fn categories_create_subcategory_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "categories",
+        "create",
+        "New Category",
+        "--parent-id",
+        "cat_shops",
+    ]));
+}
+
+#[test]
+fn categories_create_dry_run_preview_table_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "categories",
+        "create",
+        "New Category",
+        "--emoji",
+        "😀",
+        "--parent",
+        "Other",
+        "--budget-unassigned-amount",
+        "150",
+        "--dry-run-preview",
+    ]));
+}
+
+#[test]
+fn categories_create_dry_run_preview_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "categories",
+        "create",
+        "New Category",
+        "--excluded",
+        "--dry-run-preview",
+    ]));
+}
+
 #[test]
 fn categories_create_table_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -233,6 +649,105 @@ fn categories_create_table_snapshot() {
     ]));
 }
 
+#[test]
+fn recurrings_create_batch_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "recurrings",
+        "create-batch",
+        "tests/fixtures/recurrings_batch.csv",
+    ]));
+}
+
+#[test]
+fn recurrings_create_batch_json_snapshot() {
+    insta::assert_snapshot!(run_failing(&[
+        "--yes",
+        "--output",
+        "json",
+        "recurrings",
+        "create-batch",
+        "tests/fixtures/recurrings_batch.csv",
+    ]));
+}
+
+#[test]
+fn categories_list_can_delete_only_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_recurring_categories",
+        &["categories", "list", "--can-delete-only"]
+    ));
+}
+
+#[test]
+fn categories_list_cannot_delete_only_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_recurring_categories",
+        &["categories", "list", "--cannot-delete-only"]
+    ));
+}
+
+#[test]
+fn budgets_set_from_csv_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "budgets",
+        "set-from-csv",
+        "tests/fixtures/budgets.csv",
+    ]));
+}
+
+#[test]
+fn budgets_set_from_csv_dry_run_month_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "budgets",
+        "set-from-csv",
+        "tests/fixtures/budgets.csv",
+        "--month",
+        "2026-01",
+    ]));
+}
+
+#[test]
+fn recurrings_list_category_name_snapshot() {
+    insta::assert_snapshot!(run_with_fixtures(
+        "tests/fixtures/graphql_recurring_categories",
+        &["recurrings", "list", "--category", "housing"]
+    ));
+}
+
+#[test]
+fn recurrings_list_name_exact_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "recurrings",
+        "list",
+        "--name-exact",
+        "gym membership"
+    ]));
+}
+
+#[test]
+fn recurrings_list_sort_name_snapshot() {
+    insta::assert_snapshot!(run(&["recurrings", "list", "--sort", "name"]));
+}
+
+#[test]
+fn recurrings_list_sort_frequency_snapshot() {
+    insta::assert_snapshot!(run(&["recurrings", "list", "--sort", "frequency"]));
+}
+
+#[test]
+fn recurrings_list_sort_category_id_desc_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "recurrings",
+        "list",
+        "--sort",
+        "category-id",
+        "--sort-desc"
+    ]));
+}
+
 #[test]
 fn recurrings_show_table_snapshot() {
     insta::assert_snapshot!(run(&["recurrings", "show", "rec_1"]));
@@ -243,6 +758,16 @@ fn recurrings_list_filtered_snapshot() {
     insta::assert_snapshot!(run(&["recurrings", "list", "--category-id", "cat_housing"]));
 }
 
+#[test]
+fn recurrings_list_no_category_snapshot() {
+    insta::assert_snapshot!(run(&["recurrings", "list", "--no-category"]));
+}
+
+#[test]
+fn recurrings_list_with_amount_snapshot() {
+    insta::assert_snapshot!(run(&["recurrings", "list", "--with-amount"]));
+}
+
 #[test]
 fn recurrings_create_table_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -255,6 +780,34 @@ fn recurrings_create_table_snapshot() {
     ]));
 }
 
+#[test]
+fn recurrings_create_with_name_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--yes",
+        "recurrings",
+        "create",
+        "txn_1",
+        "--frequency",
+        "monthly",
+        "--name",
+        "Rent",
+    ]));
+}
+
+#[test]
+fn recurrings_create_with_name_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "recurrings",
+        "create",
+        "txn_1",
+        "--frequency",
+        "monthly",
+        "--name",
+        "Rent",
+    ]));
+}
+
 #[test]
 fn recurrings_edit_table_snapshot() {
     insta::assert_snapshot!(run(&[
@@ -272,6 +825,73 @@ fn recurrings_edit_table_snapshot() {
     ]));
 }
 
+#[test]
+fn tags_list_table_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list"]));
+}
+
+#[test]
+fn tags_list_table_no_truncate_ids_snapshot() {
+    insta::assert_snapshot!(run(&["--no-truncate-ids", "tags", "list"]));
+}
+
+#[test]
+fn tags_list_sort_name_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list", "--sort", "name"]));
+}
+
+#[test]
+fn tags_list_sort_api_order_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list", "--sort", "api-order"]));
+}
+
+#[test]
+fn tags_list_sort_color_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list", "--sort", "color"]));
+}
+
+#[test]
+fn tags_list_with_counts_table_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list", "--with-counts"]));
+}
+
+#[test]
+fn tags_list_with_counts_json_snapshot() {
+    insta::assert_snapshot!(run(&["--output", "json", "tags", "list", "--with-counts"]));
+}
+
+#[test]
+fn budgets_list_table_snapshot() {
+    insta::assert_snapshot!(run(&["budgets", "list"]));
+}
+
+#[test]
+fn budgets_list_over_budget_snapshot() {
+    insta::assert_snapshot!(run(&["budgets", "list", "--over-budget"]));
+}
+
+#[test]
+fn budgets_list_under_budget_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "budgets",
+        "list",
+        "--under-budget"
+    ]));
+}
+
+#[test]
+fn budgets_rollover_enable_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "budgets",
+        "rollover",
+        "cat_other",
+        "--enable"
+    ]));
+}
+
 #[test]
 fn budgets_month_table_snapshot() {
     insta::assert_snapshot!(run(&["budgets", "month"]));
@@ -281,3 +901,218 @@ fn budgets_month_table_snapshot() {
 fn budgets_month_json_snapshot() {
     insta::assert_snapshot!(run(&["--output", "json", "budgets", "month"]));
 }
+
+#[test]
+fn recurrings_list_with_last_seen_snapshot() {
+    insta::assert_snapshot!(run(&["recurrings", "list", "--with-last-seen"]));
+}
+
+#[test]
+fn recurrings_show_with_transactions_table_snapshot() {
+    insta::assert_snapshot!(run(&["recurrings", "show", "rec_1", "--with-transactions"]));
+}
+
+#[test]
+fn recurrings_show_with_transactions_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "recurrings",
+        "show",
+        "rec_1",
+        "--with-transactions",
+        "1",
+    ]));
+}
+
+#[test]
+fn env_table_snapshot() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("copilot"));
+    for name in [
+        "COPILOT_TOKEN_FILE",
+        "COPILOT_BASE_URL",
+        "COPILOT_SESSION_DIR",
+        "COPILOT_TOKEN_HELPER",
+        "COPILOT_HTTP_TIMEOUT_SECS",
+        "COPILOT_HTTP_CONNECT_TIMEOUT_SECS",
+        "XDG_CONFIG_HOME",
+        "NO_COLOR",
+        "RUST_LOG",
+    ] {
+        cmd.env_remove(name);
+    }
+    cmd.env("HOME", "/home/tester");
+    cmd.env("COLUMNS", "120");
+    cmd.env("COPILOT_TOKEN", "secret-token-abcd1234");
+    cmd.env("COPILOT_FIXTURES_DIR", "tests/fixtures/graphql");
+    cmd.args(["env"]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    insta::assert_snapshot!(String::from_utf8(out).unwrap());
+}
+
+#[test]
+fn tags_list_color_name_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list", "--color-name", "RED1"]));
+}
+
+#[test]
+fn tags_list_no_color_name_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list", "--no-color-name"]));
+}
+
+#[test]
+fn tags_delete_by_name_snapshot() {
+    insta::assert_snapshot!(run(&["--dry-run", "tags", "delete", "--name", "shopping"]));
+}
+
+#[test]
+fn tags_bulk_delete_snapshot() {
+    insta::assert_snapshot!(run(&["--yes", "tags", "bulk-delete", "tag_a", "tag_b"]));
+}
+
+#[test]
+fn tags_bulk_delete_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&["--dry-run", "tags", "bulk-delete", "tag_a", "tag_b"]));
+}
+
+#[test]
+fn accounts_list_table_snapshot() {
+    insta::assert_snapshot!(run(&["accounts", "list"]));
+}
+
+#[test]
+fn accounts_list_json_snapshot() {
+    insta::assert_snapshot!(run(&["--output", "json", "accounts", "list"]));
+}
+
+#[test]
+fn accounts_show_table_snapshot() {
+    insta::assert_snapshot!(run(&["accounts", "show", "acct_2"]));
+}
+
+#[test]
+fn categories_list_template_id_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "categories",
+        "list",
+        "--template-id",
+        "tmpl_shopping"
+    ]));
+}
+
+#[test]
+fn categories_list_no_template_id_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--no-template-id"]));
+}
+
+#[test]
+fn transactions_list_pending_only_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "transactions",
+        "list",
+        "--pending-only",
+        "--fields",
+        "date,name,amount,pending"
+    ]));
+}
+
+#[test]
+fn transactions_list_exclude_pending_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "transactions",
+        "list",
+        "--exclude-pending",
+        "--fields",
+        "date,name,amount,pending"
+    ]));
+}
+
+#[test]
+fn recurrings_list_frequency_not_monthly_snapshot() {
+    insta::assert_snapshot!(run(&["recurrings", "list", "--frequency-not", "monthly"]));
+}
+
+#[test]
+fn tags_list_with_counts_sort_count_snapshot() {
+    insta::assert_snapshot!(run(&["tags", "list", "--with-counts", "--sort", "count"]));
+}
+
+#[test]
+fn budgets_history_table_snapshot() {
+    insta::assert_snapshot!(run(&["budgets", "history", "cat_shops"]));
+}
+
+#[test]
+fn budgets_history_months_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "budgets",
+        "history",
+        "cat_shops",
+        "--months",
+        "2"
+    ]));
+}
+
+#[test]
+fn transactions_list_exclude_category_id_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "transactions",
+        "list",
+        "--exclude-category-id",
+        "cat_other"
+    ]));
+}
+
+#[test]
+fn transactions_list_exclude_category_by_name_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "transactions",
+        "list",
+        "--exclude-category",
+        "shops",
+        "--exclude-category-id",
+        "cat_transfers"
+    ]));
+}
+
+#[test]
+fn categories_list_children_table_snapshot() {
+    insta::assert_snapshot!(run(&["categories", "list", "--children"]));
+}
+
+#[test]
+fn categories_list_children_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--output",
+        "json",
+        "categories",
+        "list",
+        "--children"
+    ]));
+}
+
+#[test]
+fn categories_merge_dry_run_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--dry-run",
+        "categories",
+        "merge",
+        "cat_shops",
+        "cat_other"
+    ]));
+}
+
+#[test]
+fn categories_merge_json_snapshot() {
+    insta::assert_snapshot!(run(&[
+        "--yes",
+        "--output",
+        "json",
+        "categories",
+        "merge",
+        "cat_shops",
+        "cat_other"
+    ]));
+}
@@ -1,8 +1,12 @@
 use std::io::{Read, Write};
 use std::net::TcpListener;
+use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
-use copilot_money_cli::client::{ClientMode, CopilotClient};
+use copilot_money_cli::client::{ApiError, ClientMode, CopilotClient, CopilotClientTrait};
+use predicates::prelude::PredicateBooleanExt;
+use serde_json::{Value, json};
 
 fn serve_one(status: u16, body: &'static str, assert_bearer: Option<&'static str>) -> String {
     let listener = TcpListener::bind("127.0.0.1:0").unwrap();
@@ -132,6 +136,125 @@ fn serve_two(
     format!("http://{}", addr)
 }
 
+/// Serves one response and hands back the raw request body for assertions.
+fn serve_capture(body: &'static str) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        let mut buf = Vec::new();
+        let mut header_end = None;
+        while header_end.is_none() {
+            let mut tmp = [0u8; 1024];
+            let n = stream.read(&mut tmp).unwrap();
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&tmp[..n]);
+            if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                header_end = Some(i + 4);
+            }
+        }
+
+        let header_end = header_end.expect("did not receive full headers");
+        let lower = String::from_utf8_lossy(&buf[..header_end]).to_lowercase();
+        let content_length = lower
+            .lines()
+            .find_map(|l| l.strip_prefix("content-length: "))
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        let mut body_buf = buf[header_end..].to_vec();
+        while body_buf.len() < content_length {
+            let mut tmp = vec![0u8; content_length - body_buf.len()];
+            let n = stream.read(&mut tmp).unwrap();
+            if n == 0 {
+                break;
+            }
+            body_buf.extend_from_slice(&tmp[..n]);
+        }
+        tx.send(String::from_utf8_lossy(&body_buf[..content_length]).to_string())
+            .unwrap();
+
+        let resp = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(resp.as_bytes()).unwrap();
+    });
+
+    (format!("http://{}", addr), rx)
+}
+
+/// Serves one response per body, in order (one connection each).
+fn serve_sequence(bodies: Vec<&'static str>) -> String {
+    serve_sequence_with_status(bodies.into_iter().map(|b| (200, b)).collect())
+}
+
+/// Like `serve_sequence`, with a status code per response.
+fn serve_sequence_with_status(responses: Vec<(u16, &'static str)>) -> String {
+    serve_sequence_capture(responses).0
+}
+
+/// Like `serve_sequence_with_status`, also handing back each raw request body in order.
+fn serve_sequence_capture(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut buf = Vec::new();
+            let mut header_end = None;
+            while header_end.is_none() {
+                let mut tmp = [0u8; 1024];
+                let n = stream.read(&mut tmp).unwrap();
+                if n == 0 {
+                    break;
+                }
+                buf.extend_from_slice(&tmp[..n]);
+                if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                    header_end = Some(i + 4);
+                }
+            }
+
+            let header_end = header_end.expect("did not receive full headers");
+            let lower = String::from_utf8_lossy(&buf[..header_end]).to_lowercase();
+            let content_length = lower
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length: "))
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            let mut body_buf = buf[header_end..].to_vec();
+            while body_buf.len() < content_length {
+                let mut tmp = vec![0u8; content_length - body_buf.len()];
+                let n = stream.read(&mut tmp).unwrap();
+                if n == 0 {
+                    break;
+                }
+                body_buf.extend_from_slice(&tmp[..n]);
+            }
+            // The receiver may already be gone when the caller doesn't need the bodies.
+            let _ = tx.send(String::from_utf8_lossy(&body_buf[..content_length]).to_string());
+
+            let resp = format!(
+                "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(resp.as_bytes()).unwrap();
+        }
+    });
+
+    (format!("http://{}", addr), rx)
+}
+
 #[test]
 fn http_mode_sends_bearer_and_accepts_success() {
     let base_url = serve_one(200, r#"{"data":{"user":{"id":"u1"}}}"#, Some("abc"));
@@ -141,6 +264,10 @@ fn http_mode_sends_bearer_and_accepts_success() {
         token: Some("abc".to_string()),
         token_file: tmp.path().join("token"),
         session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: None,
+        connect_timeout: None,
     });
     client.try_user_query().unwrap();
 }
@@ -154,8 +281,16 @@ fn http_mode_errors_on_graphql_errors_key() {
         token: None,
         token_file: tmp.path().join("token"),
         session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: None,
+        connect_timeout: None,
     });
-    assert!(client.try_user_query().is_err());
+    let err = client.try_user_query().unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::GraphqlError { code: None, message } if message == "nope"
+    ));
 }
 
 #[test]
@@ -171,10 +306,21 @@ fn http_mode_formats_graphql_error_with_code() {
         token: None,
         token_file: tmp.path().join("token"),
         session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: None,
+        connect_timeout: None,
     });
 
-    let err = client.try_user_query().unwrap_err().to_string();
-    assert!(err.contains("graphql error (BAD_USER_INPUT): Value does not exist"));
+    let err = client.try_user_query().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "graphql error (BAD_USER_INPUT): Value does not exist"
+    );
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::GraphqlError { code: Some(code), .. } if code == "BAD_USER_INPUT"
+    ));
 }
 
 #[test]
@@ -186,8 +332,41 @@ fn http_mode_errors_on_http_status() {
         token: None,
         token_file: tmp.path().join("token"),
         session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: None,
+        connect_timeout: None,
     });
-    assert!(client.try_user_query().is_err());
+    let err = client.try_user_query().unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::HttpError { status: 401 }
+    ));
+}
+
+#[test]
+fn http_mode_reports_unauthenticated_without_session() {
+    let base_url = serve_one(
+        401,
+        r#"{"errors":[{"extensions":{"code":"UNAUTHENTICATED"},"message":"User is not authenticated"}]}"#,
+        None,
+    );
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: Some("expired_token".to_string()),
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: None,
+        connect_timeout: None,
+    });
+    let err = client.try_user_query().unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::Unauthenticated
+    ));
 }
 
 #[test]
@@ -216,6 +395,10 @@ fn http_mode_refreshes_token_on_unauthenticated_and_retries_once() {
         token: Some("expired_token".to_string()),
         token_file: token_file.clone(),
         session_dir: Some(session_dir),
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: None,
+        connect_timeout: None,
     });
 
     client.try_user_query().unwrap();
@@ -225,3 +408,763 @@ fn http_mode_refreshes_token_on_unauthenticated_and_retries_once() {
 
     unsafe { std::env::remove_var("COPILOT_TEST_REFRESH_TOKEN") };
 }
+
+#[test]
+fn transactions_list_matches_name_contains_client_side() {
+    let (base_url, rx) = serve_capture(include_str!("fixtures/graphql/Transactions.json"));
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args([
+            "--base-url",
+            &base_url,
+            "--token",
+            "abc",
+            "--output",
+            "json",
+        ])
+        .args(["transactions", "list", "--name-contains", "amazon"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Amazon.com"))
+        .stdout(predicates::str::contains("Venmo").not());
+
+    let req: serde_json::Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(req["operationName"], "Transactions");
+    assert!(req["variables"]["filter"].get("nameContains").is_none());
+}
+
+#[test]
+fn transactions_search_matches_query_client_side() {
+    let (base_url, rx) = serve_capture(include_str!("fixtures/graphql/Transactions.json"));
+    let tmp = tempfile::tempdir().unwrap();
+    let out = assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args([
+            "--base-url",
+            &base_url,
+            "--token",
+            "abc",
+            "--output",
+            "json",
+        ])
+        .args(["transactions", "search", "amazon"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let req: serde_json::Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(req["operationName"], "Transactions");
+    assert!(req["variables"]["filter"].get("nameContains").is_none());
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Amazon.com"));
+    assert!(!out.contains("Venmo"));
+}
+
+#[test]
+fn watch_run_once_prints_only_transactions_newer_than_marker() {
+    const PAGE: &str = r#"{"data":{"transactions":{"edges":[{"cursor":"c2","node":{"id":"txn_b","date":"2025-12-02","name":"Second"}},{"cursor":"c1","node":{"id":"txn_a","date":"2025-12-01","name":"First"}}],"pageInfo":{"endCursor":"c1","hasNextPage":true}}}}"#;
+    let base_url = serve_sequence(vec![
+        PAGE,
+        PAGE,
+        r#"{"data":{"transactions":{"edges":[{"cursor":"c3","node":{"id":"txn_c","date":"2025-12-02","name":"Third"}},{"cursor":"c2","node":{"id":"txn_b","date":"2025-12-02","name":"Second"}}],"pageInfo":{"endCursor":"c2","hasNextPage":true}}}}"#,
+        r#"{"data":{"categories":[]}}"#,
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let state = tmp.path().join("state.json");
+    let watch = || {
+        let mut cmd = assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"));
+        cmd.env("HOME", tmp.path())
+            .env_remove("COPILOT_FIXTURES_DIR")
+            .args(["--base-url", &base_url, "--token", "abc"])
+            .args(["watch", "--run-once", "--fields", "name"])
+            .arg("--state-file")
+            .arg(&state);
+        cmd
+    };
+
+    // First run records the newest transaction without replaying history.
+    watch()
+        .assert()
+        .success()
+        .stdout(predicates::str::is_empty());
+    // Nothing new since: prints nothing (and doesn't page back into older history).
+    watch()
+        .assert()
+        .success()
+        .stdout(predicates::str::is_empty());
+    // A new same-day transaction is printed; the one already seen is not.
+    watch()
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Third"))
+        .stdout(predicates::str::contains("Second").not());
+}
+
+#[test]
+fn transactions_bulk_review_fetches_every_page() {
+    let base_url = serve_sequence(vec![
+        r#"{"data":{"transactions":{"edges":[{"cursor":"c1","node":{"id":"txn_page1","name":"First","isReviewed":false}}],"pageInfo":{"endCursor":"c1","hasNextPage":true}}}}"#,
+        r#"{"data":{"transactions":{"edges":[{"cursor":"c2","node":{"id":"txn_page2","name":"Second","isReviewed":false}}],"pageInfo":{"endCursor":"c2","hasNextPage":false}}}}"#,
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc", "--dry-run"])
+        .args(["transactions", "bulk-review", "--unreviewed"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "would mark 2 transaction(s) reviewed",
+        ))
+        .stdout(predicates::str::contains("txn_page2"));
+}
+
+#[test]
+fn auth_status_keeps_token_valid_when_user_info_lookup_fails() {
+    let (base_url, rx) = serve_sequence_capture(vec![
+        (200, r#"{"data":{"user":{"id":"user_1"}}}"#),
+        (
+            200,
+            r#"{"errors":[{"message":"Cannot query field \"email\" on type \"User\"."}]}"#,
+        ),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc"])
+        .args(["--output", "json", "auth", "status"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(r#""value": "true""#))
+        .stdout(predicates::str::contains("user_email").not());
+
+    let user: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(user["operationName"], "User");
+    assert!(!user["query"].as_str().unwrap().contains("email"));
+    let info: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(info["operationName"], "UserInfo");
+}
+
+#[test]
+fn unauthenticated_command_exits_with_code_3() {
+    let (base_url, _rx) = serve_capture(
+        r#"{"errors":[{"extensions":{"code":"UNAUTHENTICATED"},"message":"User is not authenticated"}]}"#,
+    );
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc"])
+        .args(["tags", "list"])
+        .assert()
+        .code(3)
+        .stderr(predicates::str::contains("unauthenticated"));
+}
+
+#[test]
+fn tags_bulk_delete_continues_past_failures() {
+    let base_url = serve_sequence(vec![
+        r#"{"data":{"deleteTag":true}}"#,
+        r#"{"errors":[{"extensions":{"code":"NOT_FOUND"},"message":"Tag not found"}]}"#,
+        r#"{"data":{"deleteTag":true}}"#,
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let out = assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc", "--yes"])
+        .args([
+            "--output",
+            "json",
+            "tags",
+            "bulk-delete",
+            "tag_a",
+            "tag_b",
+            "tag_c",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicates::str::contains("failed to delete 1 tag(s)"))
+        .get_output()
+        .stdout
+        .clone();
+
+    let rows: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(
+        rows,
+        serde_json::json!([
+            {"id": "tag_a", "deleted": true, "error": null},
+            {
+                "id": "tag_b",
+                "deleted": false,
+                "error": "graphql error (NOT_FOUND): Tag not found"
+            },
+            {"id": "tag_c", "deleted": true, "error": null},
+        ])
+    );
+}
+
+#[test]
+fn tags_bulk_delete_table_output_fails_on_partial_failure() {
+    let base_url = serve_sequence(vec![
+        r#"{"errors":[{"message":"boom"}]}"#,
+        r#"{"data":{"deleteTag":true}}"#,
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc", "--yes"])
+        .args(["tags", "bulk-delete", "tag_a", "tag_b"])
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("graphql error: boom"))
+        .stdout(predicates::str::contains("tag_b"))
+        .stderr(predicates::str::contains("failed to delete 1 tag(s)"));
+}
+
+#[test]
+fn transactions_list_skip_pages_discards_earlier_pages() {
+    let base_url = serve_sequence(vec![
+        r#"{"data":{"transactions":{"edges":[{"cursor":"c1","node":{"id":"txn_page1","name":"First"}}],"pageInfo":{"endCursor":"c1","hasNextPage":true}}}}"#,
+        r#"{"data":{"transactions":{"edges":[{"cursor":"c2","node":{"id":"txn_page2","name":"Second"}}],"pageInfo":{"endCursor":"c2","hasNextPage":false}}}}"#,
+        r#"{"data":{"categories":[]}}"#,
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let out = assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args([
+            "--base-url",
+            &base_url,
+            "--token",
+            "abc",
+            "--output",
+            "json",
+        ])
+        .args(["transactions", "list", "--skip-pages", "1"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let out: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let ids = out["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["txn_page2"]);
+}
+
+#[test]
+fn http_mode_retries_server_errors_with_backoff() {
+    let base_url = serve_sequence_with_status(vec![
+        (500, r#"{"data":null}"#),
+        (502, "bad gateway"),
+        (200, r#"{"data":{"user":{"id":"u1"}}}"#),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 2,
+        retry_delay: Duration::from_millis(1),
+        timeout: None,
+        connect_timeout: None,
+    });
+    client.try_user_query().unwrap();
+}
+
+#[test]
+fn http_mode_gives_up_after_max_retries() {
+    let base_url =
+        serve_sequence_with_status(vec![(503, r#"{"data":null}"#), (503, r#"{"data":null}"#)]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 1,
+        retry_delay: Duration::from_millis(1),
+        timeout: None,
+        connect_timeout: None,
+    });
+    let err = client.try_user_query().unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::HttpError { status: 503 }
+    ));
+}
+
+#[test]
+fn http_mode_reports_status_when_retries_end_on_non_json_body() {
+    let base_url = serve_sequence_with_status(vec![(502, "bad gateway"), (502, "bad gateway")]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 1,
+        retry_delay: Duration::from_millis(1),
+        timeout: None,
+        connect_timeout: None,
+    });
+    let err = client.try_user_query().unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::HttpError { status: 502 }
+    ));
+}
+
+#[test]
+fn http_mode_does_not_retry_mutations_on_server_errors() {
+    // A retry would get the success response; the mutation must surface the 500 instead.
+    let (base_url, rx) = serve_sequence_capture(vec![
+        (500, r#"{"data":null}"#),
+        (200, r#"{"data":{"deleteTag":true}}"#),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 3,
+        retry_delay: Duration::from_millis(1),
+        timeout: None,
+        connect_timeout: None,
+    });
+    let err = client.delete_tag(&"tag_1".into()).unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::HttpError { status: 500 }
+    ));
+    rx.recv().unwrap();
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn http_mode_does_not_retry_client_errors() {
+    // Only one response is served, so a retry would fail to connect instead.
+    let base_url = serve_sequence_with_status(vec![(400, r#"{"errors":[{"message":"bad"}]}"#)]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 3,
+        retry_delay: Duration::from_millis(1),
+        timeout: None,
+        connect_timeout: None,
+    });
+    let err = client.try_user_query().unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::GraphqlError { message, .. } if message == "bad"
+    ));
+}
+
+#[test]
+fn recurrings_edit_passes_raw_json_through() {
+    for (flag, json, expected_input) in [
+        (
+            "--rule-json",
+            r#"{"nameContains":"netflix","minAmount":15.49,"extra":[1,{"x":null}]}"#,
+            serde_json::json!({
+                "rule": {"nameContains": "netflix", "minAmount": 15.49, "extra": [1, {"x": null}]}
+            }),
+        ),
+        (
+            "--input-json",
+            r#"{"rule":{"maxAmount":20},"recalculateOnlyForFuture":true}"#,
+            serde_json::json!({"rule": {"maxAmount": 20}, "recalculateOnlyForFuture": true}),
+        ),
+    ] {
+        let (base_url, rx) = serve_capture(include_str!("fixtures/graphql/EditRecurring.json"));
+        let tmp = tempfile::tempdir().unwrap();
+        assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+            .env("HOME", tmp.path())
+            .env_remove("COPILOT_FIXTURES_DIR")
+            .args(["--base-url", &base_url, "--token", "abc", "--yes"])
+            .args(["recurrings", "edit", "rec_1", flag, json])
+            .assert()
+            .success();
+
+        let req: serde_json::Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+        assert_eq!(req["operationName"], "EditRecurring");
+        assert_eq!(req["variables"]["input"], expected_input);
+    }
+}
+
+#[test]
+fn transactions_list_after_id_continues_after_that_transaction() {
+    let (base_url, rx) = serve_sequence_capture(vec![
+        (
+            200,
+            r#"{"data":{"transactions":{"edges":[{"cursor":"c1","node":{"id":"txn_a","name":"A"}},{"cursor":"c2","node":{"id":"txn_b","name":"B"}}],"pageInfo":{"endCursor":"c2","hasNextPage":true}}}}"#,
+        ),
+        (
+            200,
+            r#"{"data":{"transactions":{"edges":[{"cursor":"c3","node":{"id":"txn_c","name":"C"}},{"cursor":"c4","node":{"id":"txn_d","name":"D"}}],"pageInfo":{"endCursor":"c4","hasNextPage":true}}}}"#,
+        ),
+        (
+            200,
+            r#"{"data":{"transactions":{"edges":[{"cursor":"c4","node":{"id":"txn_d","name":"D"}}],"pageInfo":{"endCursor":"c4","hasNextPage":false}}}}"#,
+        ),
+        (200, r#"{"data":{"categories":[]}}"#),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args([
+            "--base-url",
+            &base_url,
+            "--token",
+            "abc",
+            "--output",
+            "json",
+        ])
+        .args([
+            "transactions",
+            "list",
+            "--page-size",
+            "2",
+            "--after-id",
+            "txn_c",
+        ])
+        .assert()
+        .success();
+
+    let afters = (0..3)
+        .map(|_| {
+            let req: serde_json::Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+            assert_eq!(req["operationName"], "Transactions");
+            req["variables"]["after"].clone()
+        })
+        .collect::<Vec<_>>();
+    // Scans from the start, then fetches from txn_c's own edge cursor, not the page's end.
+    assert_eq!(
+        afters,
+        vec![serde_json::Value::Null, "c2".into(), "c3".into()]
+    );
+}
+
+#[test]
+fn transactions_list_after_id_errors_when_not_found() {
+    let base_url = serve_sequence(vec![
+        r#"{"data":{"transactions":{"edges":[{"cursor":"c1","node":{"id":"txn_a","name":"A"}}],"pageInfo":{"endCursor":"c1","hasNextPage":false}}}}"#,
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc"])
+        .args(["transactions", "list", "--after-id", "txn_missing"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "--after-id: transaction txn_missing not found",
+        ));
+}
+
+/// Accepts one connection right away but waits `delay` before answering.
+fn serve_slow(delay: Duration, body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut tmp = [0u8; 4096];
+        let _ = stream.read(&mut tmp);
+        thread::sleep(delay);
+        let resp = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(resp.as_bytes());
+    });
+
+    format!("http://{}", addr)
+}
+
+#[test]
+fn http_mode_request_timeout_covers_slow_responses() {
+    let base_url = serve_slow(Duration::from_secs(3), r#"{"data":{"user":{"id":"u1"}}}"#);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: Some(Duration::from_millis(300)),
+        connect_timeout: None,
+    });
+    let err = client.try_user_query().unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::NetworkError(e) if e.is_timeout()
+    ));
+}
+
+#[test]
+fn http_mode_connect_timeout_does_not_cut_off_slow_responses() {
+    let base_url = serve_slow(
+        Duration::from_millis(600),
+        r#"{"data":{"user":{"id":"u1"}}}"#,
+    );
+    let tmp = tempfile::tempdir().unwrap();
+    let client = CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file: tmp.path().join("token"),
+        session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: Some(Duration::from_secs(10)),
+        connect_timeout: Some(Duration::from_millis(200)),
+    });
+    client.try_user_query().unwrap();
+}
+
+#[test]
+fn transactions_list_since_cursor_reads_newest_first_until_the_marker() {
+    // The feed is newest first: page 1 is all new, page 2 reaches the saved transaction.
+    let (base_url, rx) = serve_sequence_capture(vec![
+        (
+            200,
+            r#"{"data":{"transactions":{"edges":[{"cursor":"c1","node":{"id":"txn_new2","date":"2025-12-16","name":"New 2"}},{"cursor":"c2","node":{"id":"txn_new1","date":"2025-12-15","name":"New 1"}}],"pageInfo":{"endCursor":"c2","hasNextPage":true}}}}"#,
+        ),
+        (
+            200,
+            r#"{"data":{"transactions":{"edges":[{"cursor":"c3","node":{"id":"txn_old","date":"2025-12-14","name":"Old"}},{"cursor":"c4","node":{"id":"txn_older","date":"2025-12-13","name":"Older"}}],"pageInfo":{"endCursor":"c4","hasNextPage":true}}}}"#,
+        ),
+        (200, r#"{"data":{"categories":[]}}"#),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let out = assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args([
+            "--base-url",
+            &base_url,
+            "--token",
+            "abc",
+            "--output",
+            "json",
+        ])
+        .args(["transactions", "list", "--page-size", "2"])
+        .args(["--since-cursor", "2025-12-14:txn_old"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "since-cursor: 2025-12-16:txn_new2",
+        ))
+        .get_output()
+        .stdout
+        .clone();
+
+    let first: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(first["variables"]["after"], Value::Null);
+    let second: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(second["variables"]["after"], "c2");
+    let out: Value = serde_json::from_slice(&out).unwrap();
+    let ids = out["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["txn_new2", "txn_new1"]);
+}
+
+#[test]
+fn transactions_list_since_cursor_keeps_cursor_when_nothing_is_new() {
+    let base_url = serve_sequence(vec![
+        r#"{"data":{"transactions":{"edges":[{"cursor":"c1","node":{"id":"txn_old","date":"2025-12-14","name":"Old"}}],"pageInfo":{"endCursor":"c1","hasNextPage":true}}}}"#,
+        r#"{"data":{"categories":[]}}"#,
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc"])
+        .args([
+            "transactions",
+            "list",
+            "--since-cursor",
+            "2025-12-14:txn_old",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("txn_old").not())
+        .stderr(predicates::str::contains(
+            "since-cursor: 2025-12-14:txn_old",
+        ));
+}
+
+fn http_client_for(base_url: String, token_file: std::path::PathBuf) -> CopilotClient {
+    CopilotClient::new(ClientMode::Http {
+        base_url,
+        token: None,
+        token_file,
+        session_dir: None,
+        max_retries: 0,
+        retry_delay: Duration::ZERO,
+        timeout: None,
+        connect_timeout: None,
+    })
+}
+
+#[cfg(not(feature = "batch-queries"))]
+#[test]
+fn graphql_batch_without_feature_sends_one_request_per_operation() {
+    let (base_url, rx) = serve_sequence_capture(vec![
+        (200, r#"{"data":{"user":{"id":"u1"}}}"#),
+        (200, r#"{"data":{"tags":[]}}"#),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = http_client_for(base_url, tmp.path().join("token"));
+
+    let out = client
+        .graphql_batch(&[
+            ("User", "query User { user { id } }", json!({})),
+            ("Tags", "query Tags { tags { id } }", json!({})),
+        ])
+        .unwrap();
+    assert_eq!(out[0]["data"]["user"]["id"], "u1");
+    assert_eq!(out[1]["data"]["tags"], json!([]));
+
+    let first: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    let second: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(first["operationName"], "User");
+    assert_eq!(second["operationName"], "Tags");
+}
+
+#[cfg(feature = "batch-queries")]
+#[test]
+fn graphql_batch_sends_operations_as_one_array_request() {
+    let (base_url, rx) = serve_sequence_capture(vec![(
+        200,
+        r#"[{"data":{"user":{"id":"u1"}}},{"data":{"tags":[]}}]"#,
+    )]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = http_client_for(base_url, tmp.path().join("token"));
+
+    let out = client
+        .graphql_batch(&[
+            ("User", "query User { user { id } }", json!({})),
+            ("Tags", "query Tags { tags { id } }", json!({"x": 1})),
+        ])
+        .unwrap();
+    assert_eq!(out[0]["data"]["user"]["id"], "u1");
+    assert_eq!(out[1]["data"]["tags"], json!([]));
+
+    let sent: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    let sent = sent.as_array().expect("batched payload is an array");
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0]["operationName"], "User");
+    assert_eq!(sent[1]["operationName"], "Tags");
+    assert_eq!(sent[1]["variables"]["x"], 1);
+}
+
+#[cfg(feature = "batch-queries")]
+#[test]
+fn graphql_batch_falls_back_when_server_rejects_arrays() {
+    let (base_url, rx) = serve_sequence_capture(vec![
+        (
+            400,
+            r#"{"errors":[{"message":"Must provide query string."}]}"#,
+        ),
+        (200, r#"{"data":{"user":{"id":"u1"}}}"#),
+        (200, r#"{"data":{"tags":[]}}"#),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = http_client_for(base_url, tmp.path().join("token"));
+
+    let out = client
+        .graphql_batch(&[
+            ("User", "query User { user { id } }", json!({})),
+            ("Tags", "query Tags { tags { id } }", json!({})),
+        ])
+        .unwrap();
+    assert_eq!(out.len(), 2);
+    assert_eq!(out[0]["data"]["user"]["id"], "u1");
+
+    let batched: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert!(batched.is_array());
+    let first: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(first["operationName"], "User");
+}
+
+#[cfg(feature = "batch-queries")]
+#[test]
+fn graphql_batch_surfaces_per_operation_errors() {
+    let (base_url, _rx) = serve_sequence_capture(vec![(
+        200,
+        r#"[{"data":{"user":{"id":"u1"}}},{"errors":[{"message":"nope"}]}]"#,
+    )]);
+    let tmp = tempfile::tempdir().unwrap();
+    let client = http_client_for(base_url, tmp.path().join("token"));
+
+    let err = client
+        .graphql_batch(&[
+            ("User", "query User { user { id } }", json!({})),
+            ("Tags", "query Tags { tags { id } }", json!({})),
+        ])
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<ApiError>().unwrap(),
+        ApiError::GraphqlError { message, .. } if message == "nope"
+    ));
+}
+
+#[test]
+fn categories_merge_fetches_only_the_source_category() {
+    let (base_url, rx) = serve_sequence_capture(vec![
+        (
+            200,
+            r#"{"data":{"categories":[{"id":"cat_shops","name":"Shopping"},{"id":"cat_other","name":"Other"}]}}"#,
+        ),
+        (
+            200,
+            r#"{"data":{"transactions":{"edges":[{"cursor":"c1","node":{"id":"txn_1","date":"2025-12-15","name":"Amazon","categoryId":"cat_shops"}}],"pageInfo":{"endCursor":"c1","hasNextPage":false}}}}"#,
+        ),
+    ]);
+    let tmp = tempfile::tempdir().unwrap();
+    assert_cmd::Command::new(assert_cmd::cargo::cargo_bin!("copilot"))
+        .env("HOME", tmp.path())
+        .env_remove("COPILOT_FIXTURES_DIR")
+        .args(["--base-url", &base_url, "--token", "abc", "--dry-run"])
+        .args(["categories", "merge", "cat_shops", "cat_other"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "would move 1 transaction(s) from Shopping to Other",
+        ));
+
+    rx.recv().unwrap();
+    let txns: Value = serde_json::from_str(&rx.recv().unwrap()).unwrap();
+    assert_eq!(
+        txns["variables"]["filter"],
+        serde_json::json!({"categoryIds": ["cat_shops"]})
+    );
+}
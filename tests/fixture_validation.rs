@@ -0,0 +1,62 @@
+use std::path::Path;
+
+/// Where the client reads each operation's payload; arrays must be arrays, the rest just
+/// present. Operations not listed here only need a `data` key.
+const EXPECTED_SHAPES: &[(&str, &str, bool)] = &[
+    ("Transactions", "/data/transactions/edges", true),
+    ("Categories", "/data/categories", true),
+    ("Recurrings", "/data/recurrings", true),
+    ("Tags", "/data/tags", true),
+    ("Accounts", "/data/accounts", true),
+    ("Budgets", "/data/categoriesTotal/budget/histories", true),
+    ("User", "/data/user", false),
+    ("UserInfo", "/data/user", false),
+    (
+        "BulkEditTransactions",
+        "/data/bulkEditTransactions/updated",
+        true,
+    ),
+    (
+        "EditTransaction",
+        "/data/editTransaction/transaction",
+        false,
+    ),
+    ("CreateTag", "/data/createTag", false),
+    ("DeleteTag", "/data/deleteTag", false),
+    ("CreateCategory", "/data/createCategory", false),
+    ("CreateRecurring", "/data/createRecurring", false),
+    ("EditRecurring", "/data/editRecurring/recurring", false),
+];
+
+#[test]
+fn graphql_fixtures_have_data_in_the_expected_shape() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/graphql");
+    std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .for_each(|path| {
+            let text = std::fs::read_to_string(&path).unwrap();
+            let json: serde_json::Value = serde_json::from_str(&text)
+                .unwrap_or_else(|e| panic!("{}: invalid JSON: {e}", path.display()));
+            assert!(
+                json.get("data").is_some_and(|d| !d.is_null()),
+                "{}: missing `data`",
+                path.display()
+            );
+
+            let op = path.file_stem().unwrap().to_string_lossy();
+            if let Some((_, pointer, is_array)) =
+                EXPECTED_SHAPES.iter().find(|(name, _, _)| *name == op)
+            {
+                let v = json
+                    .pointer(pointer)
+                    .unwrap_or_else(|| panic!("{}: missing {pointer}", path.display()));
+                assert!(
+                    !is_array || v.is_array(),
+                    "{}: {pointer} should be an array",
+                    path.display()
+                );
+            }
+        });
+}
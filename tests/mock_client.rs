@@ -0,0 +1,544 @@
+use std::cell::RefCell;
+
+use clap::Parser;
+use copilot_money_cli::cli::{Cli, run_with_client};
+use copilot_money_cli::client::{
+    Account, BudgetMonth, BulkEditFailed, BulkEditTransactionsResult, Category, CopilotClientTrait,
+    PageInfo, Recurring, Tag, Transaction, TransactionIdRef, TransactionsPage, UserInfo,
+};
+use copilot_money_cli::types::{
+    AccountId, CategoryId, ItemId, RecurringFrequency, RecurringId, TagId, TransactionId,
+};
+use serde_json::{Value, json};
+
+/// In-memory stand-in for the API: reads return clones of the stored data and mutations
+/// update it, so a later command in the same test sees the change.
+///
+/// Filters and sort orders passed to `list_transactions_page` are ignored (like fixture
+/// mode); the CLI's client-side filtering still applies.
+#[derive(Debug, Default)]
+struct MockCopilotClient {
+    state: RefCell<MockState>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct MockState {
+    transactions: Vec<Transaction>,
+    categories: Vec<Category>,
+    recurrings: Vec<Recurring>,
+    tags: Vec<Tag>,
+    accounts: Vec<Account>,
+    budget_months: Vec<BudgetMonth>,
+    next_id: usize,
+}
+
+#[derive(Debug, Default)]
+struct MockCopilotClientBuilder {
+    state: MockState,
+}
+
+impl MockCopilotClient {
+    fn builder() -> MockCopilotClientBuilder {
+        MockCopilotClientBuilder::default()
+    }
+
+    fn transactions(&self) -> Vec<Transaction> {
+        self.state.borrow().transactions.clone()
+    }
+
+    fn tags(&self) -> Vec<Tag> {
+        self.state.borrow().tags.clone()
+    }
+
+    fn next_id(&self, prefix: &str) -> String {
+        let mut state = self.state.borrow_mut();
+        state.next_id += 1;
+        format!("{prefix}_mock_{}", state.next_id)
+    }
+}
+
+impl MockCopilotClientBuilder {
+    fn transaction(mut self, txn: Transaction) -> Self {
+        self.state.transactions.push(txn);
+        self
+    }
+
+    fn category(mut self, id: &str, name: &str) -> Self {
+        self.state
+            .categories
+            .push(serde_json::from_value(json!({ "id": id, "name": name })).unwrap());
+        self
+    }
+
+    fn recurring(mut self, id: &str, name: &str, category_id: &str) -> Self {
+        self.state.recurrings.push(
+            serde_json::from_value(json!({ "id": id, "name": name, "categoryId": category_id }))
+                .unwrap(),
+        );
+        self
+    }
+
+    fn tag(mut self, id: &str, name: &str) -> Self {
+        self.state.tags.push(Tag {
+            id: id.into(),
+            name: Some(name.to_string()),
+            color_name: None,
+        });
+        self
+    }
+
+    fn build(self) -> MockCopilotClient {
+        MockCopilotClient {
+            state: RefCell::new(self.state),
+        }
+    }
+}
+
+/// Overwrites the top-level fields of `target` named in `input` (API field names), the way
+/// the server applies an edit input.
+fn apply_input<T: serde::Serialize + serde::de::DeserializeOwned>(
+    target: &T,
+    input: &Value,
+) -> anyhow::Result<T> {
+    let mut value = serde_json::to_value(target)?;
+    if let (Some(obj), Some(changes)) = (value.as_object_mut(), input.as_object()) {
+        for (k, v) in changes {
+            obj.insert(k.clone(), v.clone());
+        }
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+impl CopilotClientTrait for MockCopilotClient {
+    fn try_user_query(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        Ok(UserInfo {
+            id: "user_mock".to_string(),
+            email: None,
+        })
+    }
+
+    fn list_transactions_page(
+        &self,
+        first: usize,
+        after: Option<String>,
+        _filter: Option<Value>,
+        _sort: Option<Value>,
+    ) -> anyhow::Result<TransactionsPage> {
+        let all = self.transactions();
+        let start = after.map(|c| c.parse::<usize>()).transpose()?.unwrap_or(0);
+        let end = (start + first).min(all.len());
+        let transactions = all[start.min(end)..end].to_vec();
+        let cursors = (start..end).map(|i| Some((i + 1).to_string())).collect();
+        Ok(TransactionsPage {
+            transactions,
+            cursors,
+            page_info: PageInfo {
+                end_cursor: Some(end.to_string()),
+                has_next_page: Some(end < all.len()),
+                has_previous_page: Some(start > 0),
+                start_cursor: Some(start.to_string()),
+            },
+        })
+    }
+
+    fn list_categories(
+        &self,
+        _spend: bool,
+        _budget: bool,
+        _rollovers: bool,
+    ) -> anyhow::Result<Vec<Category>> {
+        Ok(self.state.borrow().categories.clone())
+    }
+
+    fn list_recurrings(&self) -> anyhow::Result<Vec<Recurring>> {
+        Ok(self.state.borrow().recurrings.clone())
+    }
+
+    fn list_tags(&self) -> anyhow::Result<Vec<Tag>> {
+        Ok(self.tags())
+    }
+
+    fn list_accounts(&self) -> anyhow::Result<Vec<Account>> {
+        Ok(self.state.borrow().accounts.clone())
+    }
+
+    fn list_budget_months(&self) -> anyhow::Result<Vec<BudgetMonth>> {
+        Ok(self.state.borrow().budget_months.clone())
+    }
+
+    fn bulk_edit_transactions_reviewed(
+        &self,
+        ids: Vec<TransactionIdRef>,
+        is_reviewed: bool,
+    ) -> anyhow::Result<BulkEditTransactionsResult> {
+        let mut state = self.state.borrow_mut();
+        let mut result = BulkEditTransactionsResult {
+            updated: Vec::new(),
+            failed: Vec::new(),
+        };
+        for r in ids {
+            match state.transactions.iter_mut().find(|t| t.id == r.id) {
+                Some(t) => {
+                    t.is_reviewed = Some(is_reviewed);
+                    result.updated.push(t.clone());
+                }
+                None => result.failed.push(BulkEditFailed {
+                    transaction: None,
+                    error: Some(format!("transaction {} not found", r.id)),
+                    error_code: Some("NOT_FOUND".to_string()),
+                }),
+            }
+        }
+        Ok(result)
+    }
+
+    fn edit_transaction(
+        &self,
+        _item_id: &ItemId,
+        _account_id: &AccountId,
+        id: &TransactionId,
+        input: Value,
+    ) -> anyhow::Result<Transaction> {
+        let mut state = self.state.borrow_mut();
+        let txn = state
+            .transactions
+            .iter_mut()
+            .find(|t| &t.id == id)
+            .ok_or_else(|| anyhow::anyhow!("transaction {id} not found"))?;
+        *txn = apply_input(txn, &input)?;
+        Ok(txn.clone())
+    }
+
+    fn add_transaction_to_recurring(
+        &self,
+        _item_id: &ItemId,
+        _account_id: &AccountId,
+        id: &TransactionId,
+        recurring_id: &RecurringId,
+    ) -> anyhow::Result<Transaction> {
+        let mut state = self.state.borrow_mut();
+        let txn = state
+            .transactions
+            .iter_mut()
+            .find(|t| &t.id == id)
+            .ok_or_else(|| anyhow::anyhow!("transaction {id} not found"))?;
+        txn.recurring_id = Some(recurring_id.clone());
+        Ok(txn.clone())
+    }
+
+    fn delete_tag(&self, id: &TagId) -> anyhow::Result<bool> {
+        let mut state = self.state.borrow_mut();
+        let before = state.tags.len();
+        state.tags.retain(|t| &t.id != id);
+        Ok(state.tags.len() < before)
+    }
+
+    fn create_tag(&self, name: &str, color_name: Option<&str>) -> anyhow::Result<Tag> {
+        let tag = Tag {
+            id: self.next_id("tag").into(),
+            name: Some(name.to_string()),
+            color_name: color_name.map(str::to_string),
+        };
+        self.state.borrow_mut().tags.push(tag.clone());
+        Ok(tag)
+    }
+
+    fn create_category(
+        &self,
+        input: Value,
+        _spend: bool,
+        _budget: bool,
+    ) -> anyhow::Result<Category> {
+        let base: Category = serde_json::from_value(json!({ "id": self.next_id("cat") }))?;
+        let category = apply_input(&base, &input)?;
+        self.state.borrow_mut().categories.push(category.clone());
+        Ok(category)
+    }
+
+    fn create_recurring_from_transaction(
+        &self,
+        _item_id: &ItemId,
+        _account_id: &AccountId,
+        transaction_id: &TransactionId,
+        frequency: RecurringFrequency,
+        name: Option<&str>,
+    ) -> anyhow::Result<Recurring> {
+        let txn = self
+            .transactions()
+            .into_iter()
+            .find(|t| &t.id == transaction_id)
+            .ok_or_else(|| anyhow::anyhow!("transaction {transaction_id} not found"))?;
+        let recurring: Recurring = serde_json::from_value(json!({
+            "id": self.next_id("rec"),
+            "name": name.map(str::to_string).or(txn.name),
+            "frequency": frequency,
+            "categoryId": txn.category_id,
+        }))?;
+        self.state.borrow_mut().recurrings.push(recurring.clone());
+        Ok(recurring)
+    }
+
+    fn edit_recurring(&self, id: &RecurringId, input: Value) -> anyhow::Result<Recurring> {
+        let mut state = self.state.borrow_mut();
+        let recurring = state
+            .recurrings
+            .iter_mut()
+            .find(|r| &r.id == id)
+            .ok_or_else(|| anyhow::anyhow!("recurring {id} not found"))?;
+        *recurring = apply_input(recurring, &input)?;
+        Ok(recurring.clone())
+    }
+}
+
+/// Runs `copilot <args>` against `client` and returns what the command wrote.
+fn run(client: &MockCopilotClient, args: &[&str]) -> anyhow::Result<String> {
+    let (result, out) = run_with_output(client, args);
+    result.map(|()| out)
+}
+
+/// Like `run`, but keeps the output when the command fails after writing it.
+fn run_with_output(client: &MockCopilotClient, args: &[&str]) -> (anyhow::Result<()>, String) {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = tmp.path().join("out.txt");
+    let result = Cli::try_parse_from(
+        ["copilot", "--output-file", out.to_str().unwrap()]
+            .iter()
+            .chain(args),
+    )
+    .map_err(anyhow::Error::from)
+    .and_then(|cli| run_with_client(cli, client));
+    (result, std::fs::read_to_string(out).unwrap_or_default())
+}
+
+fn txn(id: &str, date: &str, name: &str) -> Transaction {
+    Transaction::builder(id)
+        .date(date)
+        .name(name)
+        .amount(json!(12.5))
+        .item_id("item_1")
+        .account_id("acct_1")
+        .reviewed(false)
+        .build()
+}
+
+#[test]
+fn tags_list_and_create_work() {
+    let client = MockCopilotClient::builder()
+        .tag("tag_shopping", "Shopping")
+        .tag("tag_transfer", "Transfer")
+        .build();
+
+    let out = run(&client, &["tags", "list"]).unwrap();
+    assert!(out.contains("Shopping"), "{out}");
+
+    let out = run(&client, &["--dry-run", "tags", "create", "New Tag"]).unwrap();
+    assert!(out.contains("dry-run: would create tag"), "{out}");
+    assert_eq!(client.tags().len(), 2);
+
+    let out = run(&client, &["--yes", "tags", "create", "New Tag"]).unwrap();
+    assert!(out.contains("tag_mock_1"), "{out}");
+
+    let out = run(&client, &["--output", "json", "tags", "list"]).unwrap();
+    assert!(out.contains("\"New Tag\""), "{out}");
+}
+
+#[test]
+fn tags_delete_by_name_rejects_unknown_names_and_an_id() {
+    let client = MockCopilotClient::builder()
+        .tag("tag_shopping", "Shopping")
+        .build();
+
+    let err = run(&client, &["--dry-run", "tags", "delete", "--name", "Nope"]).unwrap_err();
+    assert!(err.to_string().contains("no tag named \"Nope\""), "{err:#}");
+
+    let err = run(
+        &client,
+        &["--dry-run", "tags", "delete", "tag_1", "--name", "Shopping"],
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("cannot be used with"), "{err:#}");
+
+    run(&client, &["--yes", "tags", "delete", "--name", "shopping"]).unwrap();
+    assert!(client.tags().is_empty());
+}
+
+#[test]
+fn global_date_range_filters_transactions_with_command_precedence() {
+    let client = MockCopilotClient::builder()
+        .transaction(txn("txn_1", "2025-12-15", "Venmo"))
+        .transaction(txn("txn_2", "2025-12-15", "Amazon.com"))
+        .category("cat_food", "Food")
+        .build();
+
+    let out = run(
+        &client,
+        &[
+            "--output",
+            "json",
+            "--global-date-to",
+            "2025-12-14",
+            "transactions",
+            "list",
+        ],
+    )
+    .unwrap();
+    assert!(!out.contains("txn_"), "{out}");
+
+    let out = run(
+        &client,
+        &[
+            "--output",
+            "json",
+            "--global-date-from",
+            "2025-12-16",
+            "transactions",
+            "list",
+            "--date-from",
+            "12-15-2025",
+        ],
+    )
+    .unwrap();
+    assert!(out.contains("\"id\": \"txn_1\""), "{out}");
+    assert!(out.contains("\"id\": \"txn_2\""), "{out}");
+
+    // `set-category --all-matching` narrows its match by the global range too.
+    run(
+        &client,
+        &[
+            "--yes",
+            "--global-date-to",
+            "2025-12-14",
+            "transactions",
+            "set-category",
+            "--category",
+            "Food",
+            "--all-matching",
+        ],
+    )
+    .unwrap();
+    assert!(
+        client
+            .transactions()
+            .iter()
+            .all(|t| t.category_id.is_none())
+    );
+}
+
+#[test]
+fn transactions_set_category_updates_mock_state() {
+    let client = MockCopilotClient::builder()
+        .transaction(txn("txn_1", "2025-12-15", "Venmo"))
+        .category("cat_food", "Food")
+        .build();
+
+    run(
+        &client,
+        &[
+            "--yes",
+            "transactions",
+            "set-category",
+            "txn_1",
+            "--category",
+            "food",
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        client.transactions()[0].category_id,
+        Some(CategoryId::from("cat_food"))
+    );
+}
+
+#[test]
+fn categories_merge_reports_each_transaction_and_keeps_going_past_failures() {
+    let no_item = Transaction::builder("txn_2")
+        .date("2025-12-14")
+        .name("Amazon.com")
+        .category_id("cat_shops")
+        .build();
+    let client = MockCopilotClient::builder()
+        .transaction({
+            let mut t = txn("txn_1", "2025-12-15", "Venmo");
+            t.category_id = Some("cat_shops".into());
+            t
+        })
+        .transaction(no_item)
+        .transaction(txn("txn_3", "2025-12-13", "Cafe"))
+        .category("cat_shops", "Shopping")
+        .category("cat_other", "Other")
+        .build();
+
+    let (result, out) = run_with_output(
+        &client,
+        &[
+            "--yes",
+            "--output",
+            "json",
+            "categories",
+            "merge",
+            "cat_shops",
+            "cat_other",
+        ],
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "failed to move 1 transaction(s)"
+    );
+
+    let rows: Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(rows[0]["transaction_id"], "txn_1");
+    assert_eq!(rows[0]["status"], "moved");
+    assert_eq!(rows[1]["transaction_id"], "txn_2");
+    assert_eq!(rows[1]["status"], "failed");
+    assert_eq!(rows.as_array().unwrap().len(), 2);
+
+    let categories = client
+        .transactions()
+        .into_iter()
+        .map(|t| t.category_id.map(|c| c.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        categories,
+        vec![
+            Some("cat_other".to_string()),
+            Some("cat_shops".to_string()),
+            None
+        ]
+    );
+}
+
+#[test]
+fn recurrings_list_sort_desc_keeps_name_tie_break_ascending() {
+    let client = MockCopilotClient::builder()
+        .recurring("rec_1", "Netflix", "cat_a")
+        .recurring("rec_2", "Gym", "cat_b")
+        .recurring("rec_3", "Spotify", "cat_b")
+        .recurring("rec_4", "Apple", "cat_b")
+        .build();
+
+    let out = run(
+        &client,
+        &[
+            "--output",
+            "json",
+            "recurrings",
+            "list",
+            "--sort",
+            "category-id",
+            "--sort-desc",
+        ],
+    )
+    .unwrap();
+    let rows: Value = serde_json::from_str(&out).unwrap();
+    let ids = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["rec_4", "rec_2", "rec_3", "rec_1"]);
+}
@@ -3,6 +3,7 @@ from __future__ import annotations
 
 import argparse
 import getpass
+import os
 import sys
 import time
 from pathlib import Path
@@ -76,7 +77,13 @@ def main() -> int:
 
     email = password = None
     if credentials_mode:
-        email, password = load_creds(Path(args.secrets_file).expanduser())
+        # The CLI parses the secrets file itself and hands the password over via the
+        # environment (not argv); fall back to reading the file directly.
+        env_password = os.environ.get("COPILOT_LOGIN_PASSWORD")
+        if args.email and env_password:
+            email, password = args.email.strip(), env_password
+        else:
+            email, password = load_creds(Path(args.secrets_file).expanduser())
     elif email_link:
         if args.email:
             email = args.email.strip()